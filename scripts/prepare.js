@@ -7,6 +7,12 @@ const WINDOWS_FFMPEG_BINARY =
 const FFMPEG_SOURCE_CODE =
   "https://github.com/cathiecode/send-to-vrc-ffmpeg/archive/refs/tags/n8.0.zip";
 
+// Font of text labels on captures. Licensed under the SIL Open Font License 1.1
+const LABEL_FONT =
+  "https://github.com/notofonts/noto-cjk/raw/Sans2.004/Sans/SubsetOTF/JP/NotoSansJP-Regular.otf";
+const LABEL_FONT_LICENSE =
+  "https://github.com/notofonts/noto-cjk/raw/Sans2.004/Sans/LICENSE";
+
 async function downloadFile(url, outputPath) {
   const res = await fetch(url);
   if (!res.ok) throw new Error(`Failed to download ${url}: ${res.statusText}`);
@@ -26,6 +32,16 @@ async function main() {
     FFMPEG_SOURCE_CODE,
     "./src-tauri/resources/ffmpeg-source-code.zip",
   );
+
+  fs.mkdirSync("./src-tauri/resources/fonts", { recursive: true });
+  await downloadFile(
+    LABEL_FONT,
+    "./src-tauri/resources/fonts/NotoSansJP-Regular.otf",
+  );
+  await downloadFile(
+    LABEL_FONT_LICENSE,
+    "./src-tauri/resources/fonts/LICENSE-NotoSansJP.txt",
+  );
 }
 
 main().catch((err) => {
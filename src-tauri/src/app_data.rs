@@ -18,6 +18,13 @@ impl AppData {
         self.config.as_ref().unwrap().clone()
     }
 
+    /// Like `config`, but returns the load error instead of panicking when the config is broken.
+    pub fn try_config(
+        &self,
+    ) -> Result<std::sync::Arc<std::sync::Mutex<crate::config::Config>>, AppError> {
+        self.config.clone()
+    }
+
     pub fn lock_config(&self) -> std::sync::MutexGuard<'_, crate::config::Config> {
         self.config.as_ref().unwrap().lock().unwrap()
     }
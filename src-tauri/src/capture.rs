@@ -1,10 +1,38 @@
 use crate::prelude::*;
 use tauri::{AppHandle, Emitter as _, Manager as _};
 use tauri_specta::Event as _;
-use tokio::sync::broadcast::{Receiver, Sender};
 
-static CAPTURE_THREAD_REQUEST_SENDER: std::sync::OnceLock<Sender<CaptureThreadRequest>> =
-    std::sync::OnceLock::new();
+mod annotation;
+pub(crate) mod auto_save;
+pub mod backend;
+mod desktop;
+#[cfg(windows)]
+mod dxgi;
+pub mod live_board;
+#[cfg(test)]
+mod synthetic;
+pub mod window;
+#[cfg(target_os = "linux")]
+mod x11;
+
+use backend::CaptureBackend;
+
+static CAPTURE_THREAD: std::sync::Mutex<Option<CaptureThread>> = std::sync::Mutex::new(None);
+
+static CAPTURE_STATE: std::sync::Mutex<CaptureState> = std::sync::Mutex::new(CaptureState::Idle);
+
+/// Overlays of the current capture, used to map selections back onto the captured images.
+static CAPTURE_OVERLAYS: std::sync::Mutex<Vec<CaptureOverlay>> = std::sync::Mutex::new(Vec::new());
+
+/// Region of the last cropped capture, for `repeat_last_capture`.
+static LAST_REGION: std::sync::Mutex<Option<LastRegion>> = std::sync::Mutex::new(None);
+
+/// Next action of the current capture, see `start_capture_with_action`.
+static CAPTURE_NEXT_ACTION: std::sync::Mutex<FinishCaptureNextAction> =
+    std::sync::Mutex::new(FinishCaptureNextAction::Ask);
+
+const DEFAULT_CAPTURE_DELAY_SECONDS: u32 = 3;
+const MAX_CAPTURE_DELAY_SECONDS: u32 = 60;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum CaptureThreadRequest {
@@ -12,176 +40,420 @@ pub enum CaptureThreadRequest {
     Quit,
 }
 
-pub fn create_capture_thread(app_handle: AppHandle) -> Sender<CaptureThreadRequest> {
-    let (tx, rx) = tokio::sync::broadcast::channel::<CaptureThreadRequest>(10);
+struct CaptureThread {
+    app_handle: AppHandle,
+    sender: std::sync::mpsc::Sender<CaptureThreadRequest>,
+}
 
-    std::thread::spawn(move || {
-        let result = capture_thread(app_handle, rx);
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+pub enum CaptureState {
+    Idle,
+    /// Monitors are being captured.
+    Capturing,
+    /// Overlays are shown for the user to select a region.
+    Selecting,
+    /// The selection is being cropped.
+    Finishing,
+}
 
-        match result {
-            Ok(_) => info!("Capture thread exited normally"),
-            Err(e) => error!("Capture thread exited with error: {}", e),
-        };
-    });
+impl CaptureState {
+    /// Moves to `next`, or fails without changing anything if `next` cannot follow this state.
+    fn advance(&mut self, next: CaptureState) -> Result<(), String> {
+        use CaptureState::*;
 
-    tx
+        match (*self, next) {
+            (Idle, Capturing)
+            | (Capturing, Selecting)
+            | (Selecting, Finishing)
+            | (Finishing, Selecting)
+            | (_, Idle) => {
+                *self = next;
+                Ok(())
+            }
+            (_, Capturing) => Err("A capture is already in progress".to_string()),
+            (state, next) => Err(format!("Capture cannot go from {:?} to {:?}", state, next)),
+        }
+    }
 }
 
-fn capture_thread(
-    app_handle: AppHandle,
-    mut request_receiver: Receiver<CaptureThreadRequest>,
-) -> Result<String, String> {
-    loop {
-        loop {
-            if request_receiver.blocking_recv().unwrap() == CaptureThreadRequest::Start {
-                break;
-            };
-        }
+#[derive(Debug, Clone, serde::Serialize, specta::Type, tauri_specta::Event)]
+pub struct CaptureStateEvent {
+    state: CaptureState,
+}
+
+#[derive(Debug, Clone, serde::Serialize, specta::Type, tauri_specta::Event)]
+pub struct CaptureFailedEvent {
+    /// State the capture was in when it failed.
+    stage: CaptureState,
+    message: String,
+}
 
-        let webview = app_handle.get_webview_window("main");
+fn set_state(next: CaptureState) -> Result<(), String> {
+    CAPTURE_STATE.lock().unwrap().advance(next)?;
+
+    let app_handle = CAPTURE_THREAD
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|thread| thread.app_handle.clone());
+
+    if let Some(app_handle) = app_handle {
+        CaptureStateEvent { state: next }
+            .emit(&app_handle)
+            .unwrap_or_else(|e| debug!("Failed to emit capture state: {:?}", e));
+    }
 
-        if let Some(window) = &webview {
-            if let Err(error) = window.hide() {
-                info!("Failed to hide main window: {:?}", error);
+    Ok(())
+}
+
+fn report_failure(app_handle: &AppHandle, stage: CaptureState, message: String) {
+    error!("Capture failed while {:?}: {}", stage, message);
+
+    CaptureFailedEvent { stage, message }
+        .emit(app_handle)
+        .unwrap_or_else(|e| debug!("Failed to emit capture failure: {:?}", e));
+}
+
+fn spawn_capture_thread(app_handle: AppHandle) -> CaptureThread {
+    let (sender, receiver) = std::sync::mpsc::channel();
+
+    {
+        let app_handle = app_handle.clone();
+
+        std::thread::spawn(move || {
+            // NOTE: Caught so that a bug in one capture does not disable capturing until restart.
+            // The next request finds the channel closed and starts a new thread.
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                capture_thread(&app_handle, receiver)
+            }));
+
+            match result {
+                Ok(()) => info!("Capture thread exited normally"),
+                Err(_) => {
+                    // NOTE: The overlays and the hidden main window were owned by the crashed thread
+                    close_all_overlay_windows(&app_handle);
+
+                    if let Some(window) = app_handle.get_webview_window("main") {
+                        window
+                            .show()
+                            .unwrap_or_else(|e| info!("Failed to show main window: {:?}", e));
+                    }
+
+                    let stage = *CAPTURE_STATE.lock().unwrap();
+                    report_failure(&app_handle, stage, "Capture thread crashed".to_string());
+                }
             }
-        }
 
-        std::thread::sleep(std::time::Duration::from_millis(500));
+            set_state(CaptureState::Idle).ok();
+        });
+    }
 
-        // capture every screens
-        let monitors = windows_capture::monitor::Monitor::enumerate().unwrap();
-        for (i, monitor) in monitors.iter().enumerate() {
-            // NOTE: 2nd argument must be matched with label of WebviewWindow
-            let path = get_capture_url(&app_handle, format!("capture_{}", i))
-                .map_err(|e| format!("Failed to get temp file path: {e}"))?;
+    CaptureThread { app_handle, sender }
+}
+
+fn capture_thread(
+    app_handle: &AppHandle,
+    requests: std::sync::mpsc::Receiver<CaptureThreadRequest>,
+) {
+    let mut overlay_windows = Vec::new();
+
+    // Ends when every sender is dropped
+    while let Ok(request) = requests.recv() {
+        match request {
+            CaptureThreadRequest::Start => {
+                // NOTE: A new capture replaces overlays left over from a failed one
+                close_overlay_windows(&mut overlay_windows);
+
+                let result = window::with_main_window_hidden(app_handle, || {
+                    open_overlay_windows(app_handle)
+                });
 
-            match capture_monitor(&path, *monitor) {
-                Ok(_) => info!("Captured monitor {} to {:?}", i, path),
-                Err(e) => info!("Failed to capture monitor {}: {}", i, e),
+                match result {
+                    Ok(windows) => {
+                        overlay_windows = windows;
+
+                        if let Err(e) = set_state(CaptureState::Selecting) {
+                            info!("Capture was stopped while capturing: {}", e);
+                            close_overlay_windows(&mut overlay_windows);
+                        }
+                    }
+                    Err(e) => {
+                        report_failure(app_handle, CaptureState::Capturing, e);
+                        set_state(CaptureState::Idle).ok();
+                    }
+                }
+            }
+            CaptureThreadRequest::Quit => {
+                close_overlay_windows(&mut overlay_windows);
+                set_state(CaptureState::Idle).ok();
             }
         }
+    }
+}
 
-        info!("Finished capturing all monitors.");
+/// Captures every monitor and opens an overlay on each to select a region in.
+fn open_overlay_windows(app_handle: &AppHandle) -> Result<Vec<tauri::WebviewWindow>, String> {
+    let monitors = backend::default_backend().and_then(|backend| {
+        capture_monitors(backend.as_ref(), |i| {
+            // NOTE: 2nd argument must be matched with label of WebviewWindow
+            get_capture_url(app_handle, format!("capture_{}", i))
+                .map_err(|e| format!("Failed to get temp file path: {e}"))
+        })
+    })?;
+
+    info!("Finished capturing all monitors.");
+
+    let tauri_monitors = app_handle
+        .available_monitors()
+        .map_err(|e| format!("Failed to get monitors: {e}"))?;
 
-        let tauri_monitors = app_handle
-            .available_monitors()
-            .expect("Failed to get monitor");
+    let displays = tauri_monitors
+        .iter()
+        .map(display_geometry)
+        .collect::<Vec<_>>();
 
-        for (i, monitor) in monitors.iter().enumerate() {
-            let tauri_monitor = tauri_monitors.get(i).ok_or("Not enough monitors")?.clone();
+    // NOTE: The backend and Tauri may enumerate monitors in different orders
+    let pairs = pair_displays(&monitors, &displays);
 
+    let mut overlays = Vec::new();
+    let mut windows = Vec::new();
+
+    for (monitor, display_index) in monitors.iter().zip(pairs) {
+        let Some(display_index) = display_index else {
             info!(
-                "Monitor {}(Tauri monitor: {}): {:?}, size: {:?}, position: {:?}",
-                i,
-                monitor
-                    .name()
-                    .map_err(|e| format!("Monitor {} has no name: {:?}", i, e))?,
-                tauri_monitor.name(),
-                tauri_monitor.size(),
-                tauri_monitor.position()
+                "Monitor {}({}) has no matching Tauri monitor",
+                monitor.index, monitor.name
             );
+            continue;
+        };
 
-            let app_handle = app_handle.clone();
-
-            info!("Spawning window for monitor {}", i);
-
-            // NOTE: We add 10 pixel offset to avoid invalid monitor
-            /*let x = tauri_monitor.work_area().position.x as f64 + 10.0;
-            let y = tauri_monitor.work_area().position.y as f64 + 10.0;*/
-
-            let request_receiver = request_receiver.resubscribe();
-
-            // FIXME: Does not need to spawn a new thread for each window?
-            std::thread::spawn(move || {
-                let window = tauri::webview::WebviewWindowBuilder::new(
-                    &app_handle,
-                    format!("capture_{}", i),
-                    tauri::WebviewUrl::App("/capture/".into()),
-                )
-                .decorations(false)
-                .shadow(false)
-                .inner_size(
-                    tauri_monitor.size().width as f64,
-                    tauri_monitor.size().height as f64,
-                )
-                .position(
-                    tauri_monitor.position().x as f64,
-                    tauri_monitor.position().y as f64,
-                )
-                .transparent(true)
-                .background_color(tauri::window::Color(0, 0, 0, 0))
-                .resizable(false)
-                .build()
-                .unwrap();
-
-                // NOTE: Somehow we need to set position after the window creation to make it work correctly
-                window
-                    .set_position(*tauri_monitor.position())
-                    .unwrap_or_else(|e| info!("Failed to set position for monitor: {:?}", e));
-
-                window
-                    .set_size(*tauri_monitor.size())
-                    .unwrap_or_else(|e| info!("Failed to set size for monitor: {:?}", e));
-
-                let mut request_receiver = request_receiver;
-
-                loop {
-                    if request_receiver.blocking_recv().unwrap() == CaptureThreadRequest::Quit {
-                        break;
-                    };
-                }
+        let tauri_monitor = &tauri_monitors[display_index];
 
-                window.close().unwrap();
-            });
-        }
+        let overlay = CaptureOverlay {
+            label: format!("capture_{}", monitor.index),
+            monitor: monitor.bounds,
+            display: displays[display_index],
+        };
 
-        if let Some(window) = &webview {
-            if let Err(error) = window.show() {
-                info!("Failed to show main window: {:?}", error);
+        info!(
+            "Monitor {}({}, {:?}) -> Tauri monitor: {:?}, size: {:?}, position: {:?}, scale: {}",
+            monitor.index,
+            monitor.name,
+            monitor.bounds,
+            tauri_monitor.name(),
+            tauri_monitor.size(),
+            tauri_monitor.position(),
+            overlay.display.scale_factor
+        );
+
+        match open_overlay_window(app_handle, &overlay, tauri_monitor) {
+            Ok(window) => windows.push(window),
+            Err(e) => {
+                close_overlay_windows(&mut windows);
+                return Err(e);
             }
         }
 
-        loop {
-            if request_receiver.blocking_recv().unwrap() == CaptureThreadRequest::Quit {
-                std::thread::sleep(std::time::Duration::from_secs(1)); // wait for windows to close
-                break;
-            }
+        overlays.push(overlay);
+    }
+
+    if windows.is_empty() {
+        return Err("No captured monitor matches a display".to_string());
+    }
+
+    *CAPTURE_OVERLAYS.lock().unwrap() = overlays;
+
+    Ok(windows)
+}
+
+fn open_overlay_window(
+    app_handle: &AppHandle,
+    overlay: &CaptureOverlay,
+    tauri_monitor: &tauri::Monitor,
+) -> Result<tauri::WebviewWindow, String> {
+    info!("Spawning window for {}", overlay.label);
+
+    // NOTE: A window left by a crashed capture would make the label conflict
+    if let Some(stale) = app_handle.get_webview_window(&overlay.label) {
+        stale
+            .destroy()
+            .unwrap_or_else(|e| info!("Failed to destroy stale overlay: {:?}", e));
+    }
+
+    let (logical_x, logical_y, logical_width, logical_height) = overlay.display.logical_bounds();
+
+    let window = tauri::webview::WebviewWindowBuilder::new(
+        app_handle,
+        overlay.label.clone(),
+        tauri::WebviewUrl::App("/capture/".into()),
+    )
+    .decorations(false)
+    .shadow(false)
+    .inner_size(logical_width, logical_height)
+    .position(logical_x, logical_y)
+    .transparent(true)
+    .background_color(tauri::window::Color(0, 0, 0, 0))
+    .resizable(false)
+    .build()
+    .map_err(|e| format!("Failed to open overlay {}: {e}", overlay.label))?;
+
+    // NOTE: Somehow we need to set position after the window creation to make it work correctly
+    window
+        .set_position(*tauri_monitor.position())
+        .unwrap_or_else(|e| info!("Failed to set position for monitor: {:?}", e));
+
+    window
+        .set_size(*tauri_monitor.size())
+        .unwrap_or_else(|e| info!("Failed to set size for monitor: {:?}", e));
+
+    Ok(window)
+}
+
+fn close_overlay_windows(windows: &mut Vec<tauri::WebviewWindow>) {
+    for window in windows.drain(..) {
+        window
+            .close()
+            .unwrap_or_else(|e| info!("Failed to close overlay {}: {:?}", window.label(), e));
+    }
+}
+
+/// Closes every overlay by label, including ones whose handles were lost.
+fn close_all_overlay_windows(app_handle: &AppHandle) {
+    for (label, window) in app_handle.webview_windows() {
+        if label.starts_with("capture_") {
+            window
+                .close()
+                .unwrap_or_else(|e| info!("Failed to close overlay {}: {:?}", label, e));
         }
     }
 }
 
 pub fn init_capture_thread(app_handle: AppHandle) {
-    CAPTURE_THREAD_REQUEST_SENDER.get_or_init(|| create_capture_thread(app_handle));
+    let mut thread = CAPTURE_THREAD.lock().unwrap();
+
+    if thread.is_none() {
+        *thread = Some(spawn_capture_thread(app_handle));
+    }
 }
 
-#[tauri::command]
-#[specta::specta]
-pub fn start_capture() -> Result<(), String> {
-    let Some(sender) = CAPTURE_THREAD_REQUEST_SENDER.get() else {
+fn send_request(request: CaptureThreadRequest) -> Result<(), String> {
+    let mut thread = CAPTURE_THREAD.lock().unwrap();
+
+    let Some(current) = thread.as_mut() else {
         return Err("Capture thread is not initialized".to_string());
     };
 
-    sender
-        .send(CaptureThreadRequest::Start)
-        .map_err(|e| format!("Failed to send start request: {}", e))?;
+    if let Err(std::sync::mpsc::SendError(request)) = current.sender.send(request) {
+        info!("Capture thread is gone, starting a new one");
+
+        *current = spawn_capture_thread(current.app_handle.clone());
+
+        current
+            .sender
+            .send(request)
+            .map_err(|e| format!("Failed to send capture request: {e}"))?;
+    }
 
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn stop_capture() -> Result<(), String> {
-    let Some(sender) = CAPTURE_THREAD_REQUEST_SENDER.get() else {
+pub fn start_capture() -> Result<(), String> {
+    start_capture_with_action(FinishCaptureNextAction::Ask)
+}
+
+/// Starts a capture whose selection is sent with `next_action` when the overlay finishes with `Ask`.
+/// Fails if another capture is in progress.
+pub fn start_capture_with_action(next_action: FinishCaptureNextAction) -> Result<(), String> {
+    set_state(CaptureState::Capturing)?;
+
+    *CAPTURE_NEXT_ACTION.lock().unwrap() = next_action;
+
+    send_request(CaptureThreadRequest::Start).inspect_err(|_| {
+        set_state(CaptureState::Idle).ok();
+    })
+}
+
+#[derive(Debug, Clone, serde::Serialize, specta::Type, tauri_specta::Event)]
+pub struct CaptureCountdownEvent {
+    remaining_seconds: u32,
+}
+
+/// Starts the capture after a countdown. Uses `capture_delay_seconds` from the config when no delay is given.
+#[tauri::command]
+#[specta::specta]
+pub fn start_delayed_capture(
+    app_handle: AppHandle,
+    delay_seconds: Option<u32>,
+) -> Result<(), String> {
+    if CAPTURE_THREAD.lock().unwrap().is_none() {
         return Err("Capture thread is not initialized".to_string());
-    };
+    }
+
+    if *CAPTURE_STATE.lock().unwrap() != CaptureState::Idle {
+        return Err("A capture is already in progress".to_string());
+    }
 
-    sender
-        .send(CaptureThreadRequest::Quit)
-        .map_err(|e| format!("Failed to send quit request: {}", e))?;
+    let delay_seconds = delay_seconds
+        .or_else(|| {
+            let state = app_handle.state::<crate::app_data::AppData>();
+
+            if !state.is_healthy() {
+                return None;
+            }
+
+            state
+                .lock_config()
+                .get("capture_delay_seconds")
+                .and_then(|delay| delay.parse().ok())
+        })
+        .unwrap_or(DEFAULT_CAPTURE_DELAY_SECONDS)
+        .min(MAX_CAPTURE_DELAY_SECONDS);
+
+    info!("Starting capture in {} seconds", delay_seconds);
+
+    std::thread::spawn(move || {
+        count_down(
+            delay_seconds,
+            |remaining_seconds| {
+                if let Err(e) = (CaptureCountdownEvent { remaining_seconds }).emit(&app_handle) {
+                    info!("Failed to emit capture countdown: {:?}", e);
+                }
+            },
+            std::thread::sleep,
+        );
+
+        if let Err(e) = start_capture() {
+            error!("Failed to start delayed capture: {}", e);
+        }
+    });
 
     Ok(())
 }
 
+/// Calls `on_tick` once per second with the remaining seconds, ending with 0 right before capturing.
+fn count_down(seconds: u32, mut on_tick: impl FnMut(u32), sleep: impl Fn(std::time::Duration)) {
+    for remaining_seconds in (1..=seconds).rev() {
+        on_tick(remaining_seconds);
+        sleep(std::time::Duration::from_secs(1));
+    }
+
+    on_tick(0);
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn stop_capture() -> Result<(), String> {
+    send_request(CaptureThreadRequest::Quit)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_capture_state() -> CaptureState {
+    *CAPTURE_STATE.lock().unwrap()
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn get_capture_url_command(
@@ -203,12 +475,33 @@ pub struct NormalizedRect {
 }
 
 impl NormalizedRect {
-    fn to_rect(&self, img_width: u32, img_height: u32) -> Rect {
+    /// Maps the selection onto the captured image. Without an overlay, the selection is relative to the image.
+    fn to_rect(&self, img_width: u32, img_height: u32, overlay: Option<&CaptureOverlay>) -> Rect {
+        let to_pixel = |normalized: f32,
+                        image_size: u32,
+                        axis: fn(&backend::Bounds) -> (i32, u32)| {
+            let fraction = match overlay {
+                Some(overlay) => {
+                    let (display_position, display_size) = axis(&overlay.display.bounds);
+                    let (monitor_position, monitor_size) = axis(&overlay.monitor);
+
+                    // Desktop pixel under the selection, relative to the captured monitor
+                    let desktop = display_position as f64 + normalized as f64 * display_size as f64;
+                    (desktop - monitor_position as f64) / monitor_size.max(1) as f64
+                }
+                None => normalized as f64,
+            };
+
+            (fraction * image_size as f64)
+                .round()
+                .clamp(0.0, image_size as f64) as u32
+        };
+
         Rect {
-            x1: (self.x1 * img_width as f32) as u32,
-            y1: (self.y1 * img_height as f32) as u32,
-            x2: (self.x2 * img_width as f32) as u32,
-            y2: (self.y2 * img_height as f32) as u32,
+            x1: to_pixel(self.x1, img_width, |bounds| (bounds.x, bounds.width)),
+            y1: to_pixel(self.y1, img_height, |bounds| (bounds.y, bounds.height)),
+            x2: to_pixel(self.x2, img_width, |bounds| (bounds.x, bounds.width)),
+            y2: to_pixel(self.y2, img_height, |bounds| (bounds.y, bounds.height)),
         }
     }
 }
@@ -220,7 +513,7 @@ struct Rect {
     y2: u32,
 }
 
-#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, specta::Type)]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, specta::Type)]
 pub enum FinishCaptureNextAction {
     Ask,
     UploadImageToVideoServer,
@@ -235,18 +528,266 @@ pub fn finish_capture_with_cropped_rect(
     monitor_id: &str,
     rect: NormalizedRect,
     next_action: FinishCaptureNextAction,
+    annotations: Option<Vec<annotation::Annotation>>,
 ) -> Result<(), String> {
     info!("Finishing capture with cropped rect: {:?}", rect);
 
-    let capture_path = get_capture_url(&app_handle, monitor_id.to_string())
-        .map_err(|e| format!("Failed to get capture URL: {e}"))?;
+    finish_capture(&app_handle, || {
+        let capture_path = get_capture_url(&app_handle, monitor_id.to_string())
+            .map_err(|e| format!("Failed to get capture URL: {e}"))?;
+
+        let overlay = CAPTURE_OVERLAYS
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|overlay| overlay.label == monitor_id)
+            .cloned();
+
+        let next_action = next_action_or_default(next_action);
+        let auto_saver =
+            auto_save::AutoSaver::load(&app_handle, monitor_name(monitor_id), &next_action);
+
+        let annotations = annotations.unwrap_or_default();
+
+        let font = annotations
+            .iter()
+            .any(annotation::Annotation::is_text)
+            .then(|| annotation::label_font(&app_handle))
+            .transpose()?;
+
+        let send_request_event = crop_capture(
+            &capture_path,
+            &rect,
+            overlay.as_ref(),
+            &next_action,
+            &annotations,
+            font.as_ref(),
+            auto_saver.as_ref(),
+        )?;
+
+        if let Some(overlay) = overlay {
+            *LAST_REGION.lock().unwrap() = Some(LastRegion {
+                overlay,
+                rect,
+                next_action,
+            });
+        }
+
+        Ok(send_request_event)
+    })
+}
 
-    let result_path = crate::file::temp_file_path("cropped.png");
+/// Runs `finish` once at a time, then closes the overlays and asks to send the result.
+/// The user can select again if it fails.
+fn finish_capture(
+    app_handle: &AppHandle,
+    finish: impl FnOnce() -> Result<crate::SendRequestEvent, String>,
+) -> Result<(), String> {
+    set_state(CaptureState::Finishing)?;
 
+    let send_request_event = match finish() {
+        Ok(send_request_event) => send_request_event,
+        Err(e) => {
+            report_failure(app_handle, CaptureState::Finishing, e.clone());
+            set_state(CaptureState::Selecting).ok();
+            return Err(e);
+        }
+    };
+
+    stop_capture()?;
+
+    send_request_event
+        .emit(app_handle)
+        .map_err(|e| format!("Failed to emit send_request event: {e}"))?;
+
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+struct LastRegion {
+    overlay: CaptureOverlay,
+    rect: NormalizedRect,
+    next_action: FinishCaptureNextAction,
+}
+
+/// Captures the last selected region again without showing the overlays, and sends it the same way.
+#[tauri::command]
+#[specta::specta]
+pub async fn repeat_last_capture(app_handle: AppHandle) -> Result<(), String> {
+    // NOTE: Capturing waits for the main window to disappear, which must not block the event loop.
+    tauri::async_runtime::spawn_blocking(move || repeat_last_region(&app_handle))
+        .await
+        .map_err(|e| format!("Failed to repeat last capture: {e}"))?
+}
+
+pub fn repeat_last_region(app_handle: &AppHandle) -> Result<(), String> {
+    let last_region = LAST_REGION
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or("No region has been captured yet".to_string())?;
+
+    let auto_saver = auto_save::AutoSaver::load(
+        app_handle,
+        monitor_name(&last_region.overlay.label),
+        &last_region.next_action,
+    );
+
+    let backend = backend::default_backend()?;
+    let send_request_event = window::with_main_window_hidden(app_handle, || {
+        recapture_region(backend.as_ref(), &last_region, auto_saver.as_ref())
+    })?;
+
+    send_request_event
+        .emit(app_handle)
+        .map_err(|e| format!("Failed to emit send_request event: {e}"))?;
+
+    Ok(())
+}
+
+fn recapture_region(
+    backend: &dyn CaptureBackend,
+    last_region: &LastRegion,
+    auto_saver: Option<&auto_save::AutoSaver>,
+) -> Result<crate::SendRequestEvent, String> {
+    let full = capture_overlay_monitor(backend, &last_region.overlay)?;
+    let cropped = crop_image(&full, &last_region.rect, Some(&last_region.overlay));
+
+    save_cropped(Some(&full), &cropped, &last_region.next_action, auto_saver)
+}
+
+/// Captures the monitor `overlay` was shown on again.
+fn capture_overlay_monitor(
+    backend: &dyn CaptureBackend,
+    overlay: &CaptureOverlay,
+) -> Result<image::DynamicImage, String> {
+    // NOTE: Found by geometry, since indices change when monitors are plugged in
+    let monitors = backend.monitors()?;
+    let monitor = monitors
+        .iter()
+        .find(|monitor| monitor.bounds == overlay.monitor)
+        .ok_or("The monitor of the selected region is gone".to_string())?;
+
+    Ok(image::DynamicImage::ImageRgba8(
+        backend.capture_monitor(monitor)?,
+    ))
+}
+
+/// Selection in virtual desktop pixels, which may span several monitors.
+#[derive(Debug, Clone, specta::Type, serde::Serialize, serde::Deserialize)]
+pub struct DesktopRect {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn finish_capture_with_desktop_rect(
+    app_handle: AppHandle,
+    rect: DesktopRect,
+    next_action: FinishCaptureNextAction,
+    annotations: Option<Vec<annotation::Annotation>>,
+) -> Result<(), String> {
+    info!("Finishing capture with desktop rect: {:?}", rect);
+
+    finish_capture(&app_handle, || {
+        let overlays = CAPTURE_OVERLAYS.lock().unwrap().clone();
+
+        let mut captures = Vec::new();
+
+        for overlay in &overlays {
+            let capture_path = get_capture_url(&app_handle, overlay.label.clone())
+                .map_err(|e| format!("Failed to get capture URL: {e}"))?;
+
+            // Monitors that failed to capture have no file
+            match image::open(&capture_path) {
+                Ok(image) => captures.push((overlay.monitor, image.to_rgba8())),
+                Err(e) => info!("Skipping capture {}: {}", overlay.label, e),
+            }
+        }
+
+        let next_action = next_action_or_default(next_action);
+        let auto_saver = auto_save::AutoSaver::load(&app_handle, "desktop", &next_action);
+
+        let annotations = annotations.unwrap_or_default();
+
+        let font = annotations
+            .iter()
+            .any(annotation::Annotation::is_text)
+            .then(|| annotation::label_font(&app_handle))
+            .transpose()?;
+
+        crop_desktop(
+            &captures,
+            &rect,
+            &next_action,
+            &annotations,
+            font.as_ref(),
+            auto_saver.as_ref(),
+        )
+    })
+}
+
+/// Stitches the monitor captures, crops the selection and returns the request to send the result.
+fn crop_desktop(
+    captures: &[(backend::Bounds, image::RgbaImage)],
+    rect: &DesktopRect,
+    next_action: &FinishCaptureNextAction,
+    annotations: &[annotation::Annotation],
+    font: Option<&ab_glyph::FontArc>,
+    auto_saver: Option<&auto_save::AutoSaver>,
+) -> Result<crate::SendRequestEvent, String> {
+    let desktop = desktop::VirtualDesktop::stitch(captures)?;
+
+    let mut cropped = image::DynamicImage::ImageRgba8(desktop.crop(&backend::Bounds {
+        x: rect.x,
+        y: rect.y,
+        width: rect.width,
+        height: rect.height,
+    })?);
+
+    if !annotations.is_empty() {
+        cropped = annotation::annotate(&cropped, annotations, font)?;
+    }
+
+    save_cropped(
+        Some(&image::DynamicImage::ImageRgba8(desktop.image)),
+        &cropped,
+        next_action,
+        auto_saver,
+    )
+}
+
+/// Crops the monitor capture, draws the annotations and returns the request to send the result.
+fn crop_capture(
+    capture_path: &std::path::Path,
+    rect: &NormalizedRect,
+    overlay: Option<&CaptureOverlay>,
+    next_action: &FinishCaptureNextAction,
+    annotations: &[annotation::Annotation],
+    font: Option<&ab_glyph::FontArc>,
+    auto_saver: Option<&auto_save::AutoSaver>,
+) -> Result<crate::SendRequestEvent, String> {
     let img =
-        image::open(&capture_path).map_err(|e| format!("Failed to open captured image: {e}"))?;
+        image::open(capture_path).map_err(|e| format!("Failed to open captured image: {e}"))?;
+
+    let mut cropped = crop_image(&img, rect, overlay);
 
-    let rect = rect.to_rect(img.width(), img.height());
+    if !annotations.is_empty() {
+        cropped = annotation::annotate(&cropped, annotations, font)?;
+    }
+
+    save_cropped(Some(&img), &cropped, next_action, auto_saver)
+}
+
+fn crop_image(
+    img: &image::DynamicImage,
+    rect: &NormalizedRect,
+    overlay: Option<&CaptureOverlay>,
+) -> image::DynamicImage {
+    let rect = rect.to_rect(img.width(), img.height(), overlay);
 
     let x1 = std::cmp::min(rect.x1, rect.x2);
     let y1 = std::cmp::min(rect.y1, rect.y2);
@@ -258,40 +799,143 @@ pub fn finish_capture_with_cropped_rect(
         x1, y1, width, height
     );
 
-    let cropped = img.crop_imm(
+    img.crop_imm(
         x1,
         y1,
         std::cmp::min(width, img.width() - x1),
         std::cmp::min(height, img.height() - y1),
-    );
+    )
+}
+
+/// Saves the cropped image to send, and keeps copies in the auto-save folder if enabled.
+/// `full` is `None` when nothing was cropped.
+fn save_cropped(
+    full: Option<&image::DynamicImage>,
+    cropped: &image::DynamicImage,
+    next_action: &FinishCaptureNextAction,
+    auto_saver: Option<&auto_save::AutoSaver>,
+) -> Result<crate::SendRequestEvent, String> {
+    // NOTE: A fresh workspace per capture, so a capture does not overwrite one that is still being sent.
+    let result_path = crate::jobs::create_workspace()
+        .map_err(|e| format!("Failed to create workspace: {e:?}"))?
+        .join("cropped.png");
 
     cropped
         .save(&result_path)
         .map_err(|e| format!("Failed to save cropped image: {e}"))?;
 
-    stop_capture()?;
-
-    let send_request_event = crate::SendRequestEvent {
+    Ok(crate::SendRequestEvent {
         file: result_path.to_string_lossy().to_string(),
-        mode: match next_action {
-            FinishCaptureNextAction::Ask => None,
-            FinishCaptureNextAction::UploadImageToVideoServer => {
-                Some(crate::SendRequestEventMode::UploadImageToVideoServer)
-            }
-            FinishCaptureNextAction::UploadImageToImageServer => {
-                Some(crate::SendRequestEventMode::UploadImageToImageServer)
-            }
-            FinishCaptureNextAction::UploadImageToVRChatPrint => {
-                Some(crate::SendRequestEventMode::UploadImageToVRChatPrint)
-            }
+        mode: send_request_event_mode(next_action),
+        preselected_mode: None,
+        copy: None,
+        saved_files: auto_saver
+            .map(|auto_saver| auto_saver.save(full, cropped))
+            .unwrap_or_default(),
+    })
+}
+
+/// Monitor part of an overlay label, e.g. `0` for `capture_0`.
+fn monitor_name(label: &str) -> &str {
+    label.strip_prefix("capture_").unwrap_or(label)
+}
+
+fn next_action_or_default(next_action: FinishCaptureNextAction) -> FinishCaptureNextAction {
+    match next_action {
+        FinishCaptureNextAction::Ask => CAPTURE_NEXT_ACTION.lock().unwrap().clone(),
+        next_action => next_action,
+    }
+}
+
+pub fn send_request_event_mode(
+    next_action: &FinishCaptureNextAction,
+) -> Option<crate::SendRequestEventMode> {
+    match next_action {
+        FinishCaptureNextAction::Ask => None,
+        FinishCaptureNextAction::UploadImageToVideoServer => {
+            Some(crate::SendRequestEventMode::UploadImageToVideoServer)
+        }
+        FinishCaptureNextAction::UploadImageToImageServer => {
+            Some(crate::SendRequestEventMode::UploadImageToImageServer)
+        }
+        FinishCaptureNextAction::UploadImageToVRChatPrint => {
+            Some(crate::SendRequestEventMode::UploadImageToVRChatPrint)
+        }
+    }
+}
+
+/// Physical position and size of a Tauri monitor, with its scale factor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct DisplayGeometry {
+    bounds: backend::Bounds,
+    scale_factor: f64,
+}
+
+impl DisplayGeometry {
+    /// Returns `(x, y, width, height)` in logical pixels, as the window builder expects.
+    fn logical_bounds(&self) -> (f64, f64, f64, f64) {
+        (
+            self.bounds.x as f64 / self.scale_factor,
+            self.bounds.y as f64 / self.scale_factor,
+            self.bounds.width as f64 / self.scale_factor,
+            self.bounds.height as f64 / self.scale_factor,
+        )
+    }
+}
+
+fn display_geometry(monitor: &tauri::Monitor) -> DisplayGeometry {
+    DisplayGeometry {
+        bounds: backend::Bounds {
+            x: monitor.position().x,
+            y: monitor.position().y,
+            width: monitor.size().width,
+            height: monitor.size().height,
         },
-    };
+        scale_factor: monitor.scale_factor(),
+    }
+}
 
-    send_request_event
-        .emit(&app_handle)
-        .map_err(|e| format!("Failed to emit send_request event: {e}"))?;
+/// Overlay window placed on a display, showing the capture of a monitor.
+#[derive(Debug, Clone, PartialEq)]
+struct CaptureOverlay {
+    label: String,
+    monitor: backend::Bounds,
+    display: DisplayGeometry,
+}
 
-    Ok(())
+/// Returns the index of the display showing each monitor. Displays with the same bounds win,
+/// then the one overlapping the most, so that rounding differences between APIs do not matter.
+fn pair_displays(
+    monitors: &[backend::MonitorInfo],
+    displays: &[DisplayGeometry],
+) -> Vec<Option<usize>> {
+    let mut pairs = monitors
+        .iter()
+        .map(|monitor| {
+            displays
+                .iter()
+                .position(|display| display.bounds == monitor.bounds)
+        })
+        .collect::<Vec<_>>();
+
+    for (i, monitor) in monitors.iter().enumerate() {
+        if pairs[i].is_some() {
+            continue;
+        }
+
+        pairs[i] = displays
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !pairs.contains(&Some(*index)))
+            .filter_map(|(index, display)| {
+                let overlap = display.bounds.intersection(&monitor.bounds)?;
+                Some((index, overlap.width as u64 * overlap.height as u64))
+            })
+            .max_by_key(|(_, area)| *area)
+            .map(|(index, _)| index);
+    }
+
+    pairs
 }
 
 fn get_capture_url(
@@ -308,33 +952,454 @@ fn get_capture_url(
     Ok(path)
 }
 
-fn capture_monitor(
-    path: &std::path::Path,
-    monitor: windows_capture::monitor::Monitor,
-) -> Result<(), Box<dyn std::error::Error>> {
-    // Select a monitor (primary in this example)
+/// Saves every monitor to `path_of(index)`. Monitors that fail to capture are logged and skipped.
+fn capture_monitors(
+    backend: &dyn CaptureBackend,
+    path_of: impl Fn(usize) -> Result<std::path::PathBuf, String>,
+) -> Result<Vec<backend::MonitorInfo>, String> {
+    let monitors = backend.monitors()?;
 
-    // Create a duplication session for this monitor
-    let mut dup = windows_capture::dxgi_duplication_api::DxgiDuplicationApi::new(monitor)?;
+    for monitor in &monitors {
+        let path = path_of(monitor.index)?;
 
-    for i in 0..10 {
-        // Try to grab one frame within ~33ms (about 30 FPS budget)
-        let mut frame = dup.acquire_next_frame(1000)?;
+        let result = backend.capture_monitor(monitor).and_then(|image| {
+            image
+                .save(&path)
+                .map_err(|e| format!("Failed to save capture: {e}"))
+        });
 
-        // Map the GPU image into CPU memory and save a PNG
-        // Note: The API could send an empty frame especially
-        // in the first few calls, you can check this by seeing if
-        // frame.frame_info().LastPresentTime is zero.
+        match result {
+            Ok(_) => info!("Captured monitor {} to {:?}", monitor.index, path),
+            Err(e) => info!("Failed to capture monitor {}: {}", monitor.index, e),
+        }
+    }
 
-        if frame.frame_info().LastPresentTime == 0 {
-            info!("Frame {} is empty", i);
-            continue;
+    Ok(monitors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::backend::{Bounds, MonitorInfo};
+    use super::*;
+
+    fn synthetic_backend() -> synthetic::SyntheticBackend {
+        let monitor = |index, x, color| {
+            (
+                MonitorInfo {
+                    index,
+                    name: format!("Monitor {}", index),
+                    bounds: Bounds {
+                        x,
+                        y: 0,
+                        width: 40,
+                        height: 20,
+                    },
+                },
+                image::RgbaImage::from_fn(40, 20, |x, _| {
+                    if x < 20 {
+                        image::Rgba(color)
+                    } else {
+                        image::Rgba([0, 0, 255, 255])
+                    }
+                }),
+            )
+        };
+
+        synthetic::SyntheticBackend {
+            monitors: vec![
+                monitor(0, 0, [255, 0, 0, 255]),
+                monitor(1, 40, [0, 255, 0, 255]),
+            ],
+            windows: vec![],
+        }
+    }
+
+    /// Path of each monitor's capture in a directory of its own, so tests running in parallel do not share files.
+    fn capture_paths() -> impl Fn(usize) -> Result<std::path::PathBuf, String> {
+        let directory = crate::jobs::create_workspace().unwrap();
+
+        move |index| Ok(directory.join(format!("monitor_{}.png", index)))
+    }
+
+    #[test]
+    fn captures_every_monitor() {
+        let capture_path = capture_paths();
+        let monitors = capture_monitors(&synthetic_backend(), &capture_path).unwrap();
+
+        assert_eq!(monitors.len(), 2);
+
+        let second = image::open(capture_path(1).unwrap()).unwrap().to_rgba8();
+        assert_eq!(second.dimensions(), (40, 20));
+        assert_eq!(second.get_pixel(0, 0), &image::Rgba([0, 255, 0, 255]));
+    }
+
+    #[test]
+    fn crops_capture_into_send_request() {
+        let capture_path = capture_paths();
+        capture_monitors(&synthetic_backend(), &capture_path).unwrap();
+
+        let event = crop_capture(
+            &capture_path(0).unwrap(),
+            // Dragged from bottom right to top left
+            &NormalizedRect {
+                x1: 0.5,
+                y1: 1.0,
+                x2: 0.0,
+                y2: 0.5,
+            },
+            None,
+            &FinishCaptureNextAction::UploadImageToVRChatPrint,
+            // In pixels of the cropped image
+            &[annotation::Annotation::Rectangle {
+                rect: annotation::AnnotationRect {
+                    x: 0.0,
+                    y: 0.0,
+                    width: 5.0,
+                    height: 5.0,
+                },
+                color: annotation::AnnotationColor {
+                    r: 0,
+                    g: 0,
+                    b: 255,
+                    a: 255,
+                },
+                stroke_width: None,
+            }],
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            event.mode,
+            Some(crate::SendRequestEventMode::UploadImageToVRChatPrint)
+        );
+
+        let cropped = image::open(&event.file).unwrap().to_rgba8();
+        assert_eq!(cropped.dimensions(), (20, 10));
+        assert!(cropped.enumerate_pixels().all(|(x, y, pixel)| {
+            let annotated = x < 5 && y < 5;
+            pixel
+                == &if annotated {
+                    image::Rgba([0, 0, 255, 255])
+                } else {
+                    image::Rgba([255, 0, 0, 255])
+                }
+        }));
+    }
+
+    fn display(x: i32, y: i32, width: u32, height: u32, scale_factor: f64) -> DisplayGeometry {
+        DisplayGeometry {
+            bounds: Bounds {
+                x,
+                y,
+                width,
+                height,
+            },
+            scale_factor,
+        }
+    }
+
+    fn monitor(index: usize, display: &DisplayGeometry) -> MonitorInfo {
+        MonitorInfo {
+            index,
+            name: format!("Monitor {}", index),
+            bounds: display.bounds,
+        }
+    }
+
+    /// Normalizes a selection made in the overlay's CSS pixels, as the frontend does.
+    fn select(display: &DisplayGeometry, x1: f64, y1: f64, x2: f64, y2: f64) -> NormalizedRect {
+        let (_, _, width, height) = display.logical_bounds();
+
+        NormalizedRect {
+            x1: (x1 / width) as f32,
+            y1: (y1 / height) as f32,
+            x2: (x2 / width) as f32,
+            y2: (y2 / height) as f32,
+        }
+    }
+
+    #[test]
+    fn pairs_monitors_by_geometry() {
+        let left = display(-300, 0, 300, 150, 1.0);
+        let primary = display(0, 0, 200, 100, 1.0);
+        let right = display(200, 0, 400, 200, 2.0);
+
+        // Enumerated in a different order, and the left monitor is off by a pixel
+        let monitors = [
+            monitor(0, &right),
+            monitor(1, &primary),
+            MonitorInfo {
+                index: 2,
+                name: "Left".to_string(),
+                bounds: Bounds {
+                    width: 299,
+                    ..left.bounds
+                },
+            },
+            monitor(3, &display(5000, 0, 100, 100, 1.0)),
+        ];
+
+        assert_eq!(
+            pair_displays(&monitors, &[primary, left, right]),
+            vec![Some(2), Some(0), Some(1), None]
+        );
+    }
+
+    #[test]
+    fn crops_selection_on_mixed_dpi_monitors() {
+        let primary = display(0, 0, 200, 100, 1.0);
+        let hidpi = display(200, 0, 400, 200, 2.0);
+
+        // The selected area is red in physical pixels, everything else is blue
+        let capture = |display: &DisplayGeometry, selected: Bounds| {
+            image::RgbaImage::from_fn(display.bounds.width, display.bounds.height, |x, y| {
+                if selected.contains(x as i32, y as i32) {
+                    image::Rgba([255, 0, 0, 255])
+                } else {
+                    image::Rgba([0, 0, 255, 255])
+                }
+            })
+        };
+
+        let backend = synthetic::SyntheticBackend {
+            monitors: vec![
+                (
+                    monitor(0, &hidpi),
+                    capture(
+                        &hidpi,
+                        Bounds {
+                            x: 100,
+                            y: 50,
+                            width: 200,
+                            height: 100,
+                        },
+                    ),
+                ),
+                (
+                    monitor(1, &primary),
+                    capture(
+                        &primary,
+                        Bounds {
+                            x: 20,
+                            y: 10,
+                            width: 60,
+                            height: 30,
+                        },
+                    ),
+                ),
+            ],
+            windows: vec![],
+        };
+
+        let path = capture_paths();
+
+        let monitors = capture_monitors(&backend, &path).unwrap();
+        let displays = [primary, hidpi];
+        let pairs = pair_displays(&monitors, &displays);
+
+        let crop = |index: usize, rect: NormalizedRect| {
+            let overlay = CaptureOverlay {
+                label: format!("capture_{}", index),
+                monitor: monitors[index].bounds,
+                display: displays[pairs[index].unwrap()],
+            };
+
+            let event = crop_capture(
+                &path(index).unwrap(),
+                &rect,
+                Some(&overlay),
+                &FinishCaptureNextAction::Ask,
+                &[],
+                None,
+                None,
+            )
+            .unwrap();
+
+            image::open(&event.file).unwrap().to_rgba8()
+        };
+
+        // 50x25 CSS pixels on the 2x monitor are 100x50 physical pixels
+        let cropped = crop(0, select(&hidpi, 50.0, 25.0, 150.0, 75.0));
+        assert_eq!(cropped.dimensions(), (200, 100));
+        assert!(cropped
+            .pixels()
+            .all(|pixel| pixel == &image::Rgba([255, 0, 0, 255])));
+
+        let cropped = crop(1, select(&primary, 80.0, 40.0, 20.0, 10.0));
+        assert_eq!(cropped.dimensions(), (60, 30));
+        assert!(cropped
+            .pixels()
+            .all(|pixel| pixel == &image::Rgba([255, 0, 0, 255])));
+    }
+
+    #[test]
+    fn crops_desktop_selection_across_monitors() {
+        let captures = synthetic_backend()
+            .monitors
+            .into_iter()
+            .map(|(monitor, image)| (monitor.bounds, image))
+            .collect::<Vec<_>>();
+
+        // From the blue right half of the first monitor into the green left half of the second
+        let event = crop_desktop(
+            &captures,
+            &DesktopRect {
+                x: 30,
+                y: 5,
+                width: 20,
+                height: 10,
+            },
+            &FinishCaptureNextAction::UploadImageToImageServer,
+            &[],
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            event.mode,
+            Some(crate::SendRequestEventMode::UploadImageToImageServer)
+        );
+
+        let cropped = image::open(&event.file).unwrap().to_rgba8();
+        assert_eq!(cropped.dimensions(), (20, 10));
+        assert_eq!(cropped.get_pixel(0, 0), &image::Rgba([0, 0, 255, 255]));
+        assert_eq!(cropped.get_pixel(19, 9), &image::Rgba([0, 255, 0, 255]));
+    }
+
+    #[test]
+    fn annotates_desktop_selection() {
+        let captures = synthetic_backend()
+            .monitors
+            .into_iter()
+            .map(|(monitor, image)| (monitor.bounds, image))
+            .collect::<Vec<_>>();
+
+        let event = crop_desktop(
+            &captures,
+            &DesktopRect {
+                x: 30,
+                y: 5,
+                width: 20,
+                height: 10,
+            },
+            &FinishCaptureNextAction::UploadImageToImageServer,
+            &[annotation::Annotation::Rectangle {
+                rect: annotation::AnnotationRect {
+                    x: 0.0,
+                    y: 0.0,
+                    width: 5.0,
+                    height: 5.0,
+                },
+                color: annotation::AnnotationColor {
+                    r: 255,
+                    g: 0,
+                    b: 0,
+                    a: 255,
+                },
+                stroke_width: None,
+            }],
+            None,
+            None,
+        )
+        .unwrap();
+
+        // Annotations are in pixels of the selection, not of the desktop
+        let cropped = image::open(&event.file).unwrap().to_rgba8();
+        assert_eq!(cropped.get_pixel(2, 2), &image::Rgba([255, 0, 0, 255]));
+        assert_eq!(cropped.get_pixel(19, 9), &image::Rgba([0, 255, 0, 255]));
+    }
+
+    #[test]
+    fn counts_down_to_zero() {
+        let mut ticks = vec![];
+        let slept = std::cell::Cell::new(0);
+
+        count_down(
+            3,
+            |remaining| ticks.push(remaining),
+            |_| slept.set(slept.get() + 1),
+        );
+
+        assert_eq!(ticks, vec![3, 2, 1, 0]);
+        assert_eq!(slept.get(), 3);
+
+        ticks.clear();
+        count_down(0, |remaining| ticks.push(remaining), |_| unreachable!());
+        assert_eq!(ticks, vec![0]);
+    }
+
+    #[test]
+    fn repeats_last_region_on_the_same_monitor() {
+        let mut backend = synthetic_backend();
+        let second = backend.monitors[1].0.clone();
+
+        let last_region = LastRegion {
+            overlay: CaptureOverlay {
+                label: "capture_1".to_string(),
+                monitor: second.bounds,
+                display: DisplayGeometry {
+                    bounds: second.bounds,
+                    scale_factor: 1.0,
+                },
+            },
+            rect: NormalizedRect {
+                x1: 0.0,
+                y1: 0.0,
+                x2: 0.5,
+                y2: 0.5,
+            },
+            next_action: FinishCaptureNextAction::UploadImageToVideoServer,
+        };
+
+        // Enumerated in a different order after the first capture
+        backend.monitors.reverse();
+        for (index, (monitor, _)) in backend.monitors.iter_mut().enumerate() {
+            monitor.index = index;
         }
 
-        frame.save_as_image(path, windows_capture::encoder::ImageFormat::Png)?;
+        let event = recapture_region(&backend, &last_region, None).unwrap();
+
+        assert_eq!(
+            event.mode,
+            Some(crate::SendRequestEventMode::UploadImageToVideoServer)
+        );
+
+        let cropped = image::open(&event.file).unwrap().to_rgba8();
+        assert_eq!(cropped.dimensions(), (20, 10));
+        assert!(cropped
+            .pixels()
+            .all(|pixel| pixel == &image::Rgba([0, 255, 0, 255])));
 
-        return Ok(());
+        backend.monitors.remove(0);
+        assert!(recapture_region(&backend, &last_region, None).is_err());
     }
 
-    Err("No frame captured".into())
+    #[test]
+    fn moves_through_capture_states() {
+        let mut state = CaptureState::Idle;
+
+        state.advance(CaptureState::Capturing).unwrap();
+        assert_eq!(
+            state.advance(CaptureState::Capturing),
+            Err("A capture is already in progress".to_string())
+        );
+        assert!(state.advance(CaptureState::Finishing).is_err());
+        assert_eq!(state, CaptureState::Capturing);
+
+        state.advance(CaptureState::Selecting).unwrap();
+        state.advance(CaptureState::Finishing).unwrap();
+        // Finishing twice is rejected
+        assert!(state.advance(CaptureState::Finishing).is_err());
+
+        // A failed finish goes back to selecting
+        state.advance(CaptureState::Selecting).unwrap();
+        state.advance(CaptureState::Finishing).unwrap();
+
+        state.advance(CaptureState::Idle).unwrap();
+        assert!(state.advance(CaptureState::Selecting).is_err());
+        assert_eq!(state, CaptureState::Idle);
+    }
 }
@@ -0,0 +1,925 @@
+use ab_glyph::{Font as _, ScaleFont as _};
+use tauri::Manager as _;
+
+use crate::prelude::*;
+
+/// Font of text labels, bundled by `scripts/prepare.js` because it covers Japanese.
+const LABEL_FONT_PATH: &str = "resources/fonts/NotoSansJP-Regular.otf";
+
+// NOTE: The font is several megabytes, so it is read on the first label and kept.
+static LABEL_FONT: std::sync::Mutex<Option<ab_glyph::FontArc>> = std::sync::Mutex::new(None);
+
+/// Point in pixels of the cropped image.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, specta::Type)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// Rectangle in pixels of the cropped image.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, specta::Type)]
+pub struct AnnotationRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, specta::Type)]
+pub struct AnnotationColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+/// Shape drawn on the cropped image before it is sent. Shapes are drawn in order,
+/// so a blur box covers the shapes before it.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, specta::Type)]
+pub enum Annotation {
+    Arrow {
+        from: Point,
+        to: Point,
+        color: AnnotationColor,
+        width: f32,
+    },
+    /// Outlined with `stroke_width`, or filled without it, e.g. with a translucent
+    /// color to highlight text.
+    Rectangle {
+        rect: AnnotationRect,
+        color: AnnotationColor,
+        stroke_width: Option<f32>,
+    },
+    Freehand {
+        points: Vec<Point>,
+        color: AnnotationColor,
+        width: f32,
+    },
+    /// Label of `size` pixel tall text drawn at `position` (top left). Characters the
+    /// font does not have are drawn as boxes.
+    Text {
+        position: Point,
+        text: String,
+        color: AnnotationColor,
+        size: f32,
+        background: Option<AnnotationColor>,
+    },
+    Blur {
+        rect: AnnotationRect,
+        radius: f32,
+    },
+}
+
+impl Annotation {
+    pub fn is_text(&self) -> bool {
+        matches!(self, Annotation::Text { .. })
+    }
+}
+
+/// Returns the font of text labels, reading the bundled one the first time.
+pub fn label_font(app_handle: &tauri::AppHandle) -> Result<ab_glyph::FontArc, String> {
+    let mut font = LABEL_FONT.lock().unwrap();
+
+    if let Some(font) = font.as_ref() {
+        return Ok(font.clone());
+    }
+
+    let path = app_handle
+        .path()
+        .resolve(LABEL_FONT_PATH, tauri::path::BaseDirectory::Resource)
+        .map_err(|e| format!("Failed to resolve label font path: {e}"))?;
+
+    let loaded = read_font(&path)?;
+    *font = Some(loaded.clone());
+
+    Ok(loaded)
+}
+
+fn read_font(path: &std::path::Path) -> Result<ab_glyph::FontArc, String> {
+    let bytes =
+        std::fs::read(path).map_err(|e| format!("Failed to read label font {:?}: {e}", path))?;
+
+    ab_glyph::FontArc::try_from_vec(bytes)
+        .map_err(|e| format!("Failed to load label font {:?}: {e}", path))
+}
+
+/// Draws `annotations` on `image`. Shapes without an area, e.g. from a click without a
+/// drag, are skipped. `font` is only needed for text labels.
+pub fn annotate(
+    image: &image::DynamicImage,
+    annotations: &[Annotation],
+    font: Option<&ab_glyph::FontArc>,
+) -> Result<image::DynamicImage, String> {
+    if font.is_none() && annotations.iter().any(Annotation::is_text) {
+        return Err("Text labels need a font".to_string());
+    }
+
+    let image = image.to_rgba8();
+
+    let size = tiny_skia::IntSize::from_wh(image.width(), image.height())
+        .ok_or("Failed to create canvas with image dimensions".to_string())?;
+
+    // NOTE: tiny-skia works on premultiplied alpha, e.g. for the gaps of a stitched desktop
+    let mut canvas = tiny_skia::Pixmap::from_vec(image.into_raw(), size)
+        .ok_or("Failed to create canvas from image".to_string())?;
+
+    for pixel in canvas.pixels_mut() {
+        *pixel =
+            tiny_skia::ColorU8::from_rgba(pixel.red(), pixel.green(), pixel.blue(), pixel.alpha())
+                .premultiply();
+    }
+
+    for annotation in annotations {
+        if !draw(&mut canvas, annotation, font) {
+            debug!("Skipped empty annotation: {:?}", annotation);
+        }
+    }
+
+    let (width, height) = (canvas.width(), canvas.height());
+
+    let data = canvas
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let pixel = pixel.demultiply();
+            [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+        })
+        .collect();
+
+    image::RgbaImage::from_raw(width, height, data)
+        .map(image::DynamicImage::ImageRgba8)
+        .ok_or("Failed to read annotated image".to_string())
+}
+
+/// Returns whether anything was drawn.
+fn draw(
+    canvas: &mut tiny_skia::Pixmap,
+    annotation: &Annotation,
+    font: Option<&ab_glyph::FontArc>,
+) -> bool {
+    match annotation {
+        Annotation::Arrow {
+            from,
+            to,
+            color,
+            width,
+        } => draw_arrow(canvas, *from, *to, color, *width),
+        Annotation::Rectangle {
+            rect,
+            color,
+            stroke_width,
+        } => {
+            let Some(path) = to_skia_rect(rect).map(tiny_skia::PathBuilder::from_rect) else {
+                return false;
+            };
+
+            match stroke_width {
+                Some(width) => stroke(canvas, &path, color, *width),
+                None => fill(canvas, &path, color),
+            }
+
+            true
+        }
+        Annotation::Freehand {
+            points,
+            color,
+            width,
+        } => draw_freehand(canvas, points, color, *width),
+        Annotation::Text {
+            position,
+            text,
+            color,
+            size,
+            background,
+        } => font.is_some_and(|font| {
+            draw_text(
+                canvas,
+                font,
+                *position,
+                text,
+                color,
+                *size,
+                background.as_ref(),
+            )
+        }),
+        Annotation::Blur { rect, radius } => blur(canvas, rect, *radius),
+    }
+}
+
+fn paint(color: &AnnotationColor) -> tiny_skia::Paint<'static> {
+    let mut paint = tiny_skia::Paint::default();
+    paint.set_color_rgba8(color.r, color.g, color.b, color.a);
+    paint.anti_alias = true;
+    paint
+}
+
+fn fill(canvas: &mut tiny_skia::Pixmap, path: &tiny_skia::Path, color: &AnnotationColor) {
+    canvas.fill_path(
+        path,
+        &paint(color),
+        tiny_skia::FillRule::Winding,
+        tiny_skia::Transform::identity(),
+        None,
+    );
+}
+
+fn stroke(
+    canvas: &mut tiny_skia::Pixmap,
+    path: &tiny_skia::Path,
+    color: &AnnotationColor,
+    width: f32,
+) {
+    canvas.stroke_path(
+        path,
+        &paint(color),
+        &tiny_skia::Stroke {
+            width: width.max(1.0),
+            line_cap: tiny_skia::LineCap::Round,
+            line_join: tiny_skia::LineJoin::Round,
+            ..Default::default()
+        },
+        tiny_skia::Transform::identity(),
+        None,
+    );
+}
+
+fn to_skia_rect(rect: &AnnotationRect) -> Option<tiny_skia::Rect> {
+    // NOTE: Rectangles dragged up or to the left have a negative size
+    tiny_skia::Rect::from_ltrb(
+        rect.x.min(rect.x + rect.width),
+        rect.y.min(rect.y + rect.height),
+        rect.x.max(rect.x + rect.width),
+        rect.y.max(rect.y + rect.height),
+    )
+    .filter(|rect| rect.width() > 0.0 && rect.height() > 0.0)
+}
+
+fn draw_arrow(
+    canvas: &mut tiny_skia::Pixmap,
+    from: Point,
+    to: Point,
+    color: &AnnotationColor,
+    width: f32,
+) -> bool {
+    let (dx, dy) = (to.x - from.x, to.y - from.y);
+    let length = (dx * dx + dy * dy).sqrt();
+
+    if !length.is_normal() {
+        return false;
+    }
+
+    let width = width.max(1.0);
+    // The head keeps its shape on short arrows, but never takes up more than half
+    let head_length = (width * 3.0 + 6.0).min(length / 2.0);
+    let head_half_width = head_length * 0.6;
+
+    let (ux, uy) = (dx / length, dy / length);
+    let base = Point {
+        x: to.x - ux * head_length,
+        y: to.y - uy * head_length,
+    };
+
+    let mut shaft = tiny_skia::PathBuilder::new();
+    shaft.move_to(from.x, from.y);
+    shaft.line_to(base.x, base.y);
+
+    if let Some(shaft) = shaft.finish() {
+        stroke(canvas, &shaft, color, width);
+    }
+
+    let mut head = tiny_skia::PathBuilder::new();
+    head.move_to(to.x, to.y);
+    head.line_to(base.x - uy * head_half_width, base.y + ux * head_half_width);
+    head.line_to(base.x + uy * head_half_width, base.y - ux * head_half_width);
+    head.close();
+
+    if let Some(head) = head.finish() {
+        fill(canvas, &head, color);
+    }
+
+    true
+}
+
+fn draw_freehand(
+    canvas: &mut tiny_skia::Pixmap,
+    points: &[Point],
+    color: &AnnotationColor,
+    width: f32,
+) -> bool {
+    let Some((first, rest)) = points.split_first() else {
+        return false;
+    };
+
+    // NOTE: A single point is a dot, which a stroke would not draw
+    if rest.iter().all(|point| point == first) {
+        return match tiny_skia::PathBuilder::from_circle(first.x, first.y, width.max(1.0) / 2.0) {
+            Some(dot) => {
+                fill(canvas, &dot, color);
+                true
+            }
+            None => false,
+        };
+    }
+
+    let mut path = tiny_skia::PathBuilder::new();
+    path.move_to(first.x, first.y);
+
+    for point in rest {
+        path.line_to(point.x, point.y);
+    }
+
+    match path.finish() {
+        Some(path) => {
+            stroke(canvas, &path, color, width);
+            true
+        }
+        None => false,
+    }
+}
+
+fn draw_text(
+    canvas: &mut tiny_skia::Pixmap,
+    font: &ab_glyph::FontArc,
+    position: Point,
+    text: &str,
+    color: &AnnotationColor,
+    size: f32,
+    background: Option<&AnnotationColor>,
+) -> bool {
+    if text.trim().is_empty() || size <= 0.0 {
+        return false;
+    }
+
+    let font = font.as_scaled(size);
+    let line_height = font.height() + font.line_gap();
+
+    let mut glyphs = Vec::new();
+    let mut width: f32 = 0.0;
+    let mut line_count = 0;
+
+    for (row, line) in text.lines().enumerate() {
+        let baseline = position.y + font.ascent() + row as f32 * line_height;
+        let mut caret = position.x;
+        let mut previous = None;
+
+        for c in line.chars() {
+            let id = font.glyph_id(c);
+
+            if let Some(previous) = previous {
+                caret += font.kern(previous, id);
+            }
+
+            glyphs.push(id.with_scale_and_position(size, ab_glyph::point(caret, baseline)));
+
+            caret += font.h_advance(id);
+            previous = Some(id);
+        }
+
+        width = width.max(caret - position.x);
+        line_count = row + 1;
+    }
+
+    if let Some(background) = background {
+        let padding = size / 8.0;
+
+        let rect = tiny_skia::Rect::from_xywh(
+            position.x - padding,
+            position.y - padding,
+            width + padding * 2.0,
+            line_count as f32 * line_height + padding * 2.0,
+        );
+
+        if let Some(rect) = rect {
+            canvas.fill_rect(
+                rect,
+                &paint(background),
+                tiny_skia::Transform::identity(),
+                None,
+            );
+        }
+    }
+
+    // NOTE: Glyphs are rasterized to coverage, which is then painted through as a mask
+    let Some(mut mask) = tiny_skia::Mask::new(canvas.width(), canvas.height()) else {
+        return false;
+    };
+
+    let mask_width = mask.width() as i64;
+    let mask_height = mask.height() as i64;
+
+    for glyph in glyphs {
+        let Some(outlined) = font.outline_glyph(glyph) else {
+            continue;
+        };
+
+        let bounds = outlined.px_bounds();
+
+        outlined.draw(|x, y, coverage| {
+            let x = bounds.min.x as i64 + x as i64;
+            let y = bounds.min.y as i64 + y as i64;
+
+            if !(0..mask_width).contains(&x) || !(0..mask_height).contains(&y) {
+                return;
+            }
+
+            let value = &mut mask.data_mut()[(y * mask_width + x) as usize];
+            *value = (*value).max((coverage.clamp(0.0, 1.0) * 255.0).round() as u8);
+        });
+    }
+
+    canvas.fill_rect(
+        tiny_skia::Rect::from_xywh(0.0, 0.0, canvas.width() as f32, canvas.height() as f32)
+            .expect("Canvas has a size"),
+        &paint(color),
+        tiny_skia::Transform::identity(),
+        Some(&mask),
+    );
+
+    true
+}
+
+/// Blurs the pixels inside `rect`, e.g. to hide names or chat.
+fn blur(canvas: &mut tiny_skia::Pixmap, rect: &AnnotationRect, radius: f32) -> bool {
+    let Some(rect) = to_skia_rect(rect)
+        .and_then(|rect| rect.round())
+        .and_then(|rect| {
+            rect.intersect(&tiny_skia::IntRect::from_xywh(
+                0,
+                0,
+                canvas.width(),
+                canvas.height(),
+            )?)
+        })
+    else {
+        return false;
+    };
+
+    let stride = canvas.width() as usize * 4;
+    let data = canvas.data_mut();
+
+    // NOTE: Blurring premultiplied pixels keeps transparent pixels from darkening their neighbors
+    let region = image::RgbaImage::from_fn(rect.width(), rect.height(), |x, y| {
+        let offset =
+            (rect.y() as usize + y as usize) * stride + (rect.x() as usize + x as usize) * 4;
+        image::Rgba([
+            data[offset],
+            data[offset + 1],
+            data[offset + 2],
+            data[offset + 3],
+        ])
+    });
+
+    let blurred = image::imageops::blur(&region, radius.max(1.0));
+
+    for (x, y, pixel) in blurred.enumerate_pixels() {
+        let offset =
+            (rect.y() as usize + y as usize) * stride + (rect.x() as usize + x as usize) * 4;
+        // Rounding may leave a color channel above alpha, which is not valid premultiplied
+        let alpha = pixel[3];
+        data[offset..offset + 4].copy_from_slice(&[
+            pixel[0].min(alpha),
+            pixel[1].min(alpha),
+            pixel[2].min(alpha),
+            alpha,
+        ]);
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: AnnotationColor = AnnotationColor {
+        r: 230,
+        g: 30,
+        b: 40,
+        a: 255,
+    };
+
+    /// Checkerboard with a gradient, so that blur and translucent shapes show.
+    fn background() -> image::DynamicImage {
+        image::DynamicImage::ImageRgba8(image::RgbaImage::from_fn(96, 64, |x, y| {
+            let checker = if (x / 8 + y / 8) % 2 == 0 { 60 } else { 200 };
+            image::Rgba([checker, (x * 2) as u8, (y * 3) as u8, 255])
+        }))
+    }
+
+    /// Compares against `test_data/annotation/<name>.png`. Run with `UPDATE_GOLDEN=1`
+    /// to write the expected images after an intended change.
+    fn assert_golden(name: &str, actual: &image::DynamicImage) {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data/annotation")
+            .join(format!("{}.png", name));
+
+        let actual = actual.to_rgba8();
+
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            actual.save(&path).unwrap();
+            return;
+        }
+
+        let expected = image::open(&path)
+            .unwrap_or_else(|e| panic!("Failed to open {:?}: {}", path, e))
+            .to_rgba8();
+
+        assert_eq!(actual.dimensions(), expected.dimensions());
+
+        // NOTE: Anti-aliasing may differ by a step between SIMD and scalar code
+        let differences = actual
+            .pixels()
+            .zip(expected.pixels())
+            .filter(|(a, b)| a.0.iter().zip(b.0.iter()).any(|(a, b)| a.abs_diff(*b) > 2))
+            .count();
+
+        assert_eq!(differences, 0, "{} differs from {:?}", name, path);
+    }
+
+    #[test]
+    fn draws_arrows_and_rectangles() {
+        let annotated = annotate(
+            &background(),
+            &[
+                Annotation::Rectangle {
+                    rect: AnnotationRect {
+                        x: 8.0,
+                        y: 8.0,
+                        width: 40.0,
+                        height: 20.0,
+                    },
+                    color: RED,
+                    stroke_width: Some(3.0),
+                },
+                // Highlight, dragged up and to the left
+                Annotation::Rectangle {
+                    rect: AnnotationRect {
+                        x: 88.0,
+                        y: 56.0,
+                        width: -36.0,
+                        height: -12.0,
+                    },
+                    color: AnnotationColor {
+                        r: 255,
+                        g: 240,
+                        b: 0,
+                        a: 96,
+                    },
+                    stroke_width: None,
+                },
+                Annotation::Arrow {
+                    from: Point { x: 80.0, y: 8.0 },
+                    to: Point { x: 50.0, y: 30.0 },
+                    color: RED,
+                    width: 2.0,
+                },
+            ],
+            None,
+        )
+        .unwrap();
+
+        assert_golden("arrows_and_rectangles", &annotated);
+    }
+
+    #[test]
+    fn draws_freehand_paths() {
+        let annotated = annotate(
+            &background(),
+            &[
+                Annotation::Freehand {
+                    points: (0..=20)
+                        .map(|i| Point {
+                            x: 8.0 + i as f32 * 4.0,
+                            y: 32.0 + (i as f32 / 2.0).sin() * 16.0,
+                        })
+                        .collect(),
+                    color: RED,
+                    width: 4.0,
+                },
+                Annotation::Freehand {
+                    points: vec![Point { x: 48.0, y: 56.0 }],
+                    color: RED,
+                    width: 6.0,
+                },
+            ],
+            None,
+        )
+        .unwrap();
+
+        assert_golden("freehand_paths", &annotated);
+    }
+
+    /// DejaVu Sans with only ASCII characters, since the bundled font is downloaded by `scripts/prepare.js`.
+    fn font() -> ab_glyph::FontArc {
+        read_font(
+            &std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("test_data/fonts/DejaVuSans-ASCII.ttf"),
+        )
+        .unwrap()
+    }
+
+    fn label(position: Point, text: &str, size: f32) -> Annotation {
+        Annotation::Text {
+            position,
+            text: text.to_string(),
+            color: RED,
+            size,
+            background: None,
+        }
+    }
+
+    /// Bounds of the pixels that differ between the images, as (left, top, right, bottom).
+    fn changed_bounds(
+        original: &image::DynamicImage,
+        annotated: &image::DynamicImage,
+    ) -> Option<(u32, u32, u32, u32)> {
+        let (original, annotated) = (original.to_rgba8(), annotated.to_rgba8());
+
+        annotated
+            .enumerate_pixels()
+            .filter(|(x, y, pixel)| *pixel != original.get_pixel(*x, *y))
+            .fold(None, |bounds, (x, y, _)| {
+                Some(match bounds {
+                    Some((left, top, right, bottom)) => {
+                        (x.min(left), y.min(top), x.max(right), y.max(bottom))
+                    }
+                    None => (x, y, x, y),
+                })
+            })
+    }
+
+    #[test]
+    fn draws_text_labels() {
+        let original = background();
+        let font = font();
+
+        let annotated = annotate(
+            &original,
+            &[label(Point { x: 8.0, y: 8.0 }, "Hello,\n\u{3042}", 16.0)],
+            Some(&font),
+        )
+        .unwrap();
+
+        // Two lines of 16 pixel text below and to the right of the position
+        let (left, top, right, bottom) = changed_bounds(&original, &annotated).unwrap();
+        assert!(left >= 8 && top >= 8, "drawn from {}, {}", left, top);
+        assert!(
+            right < 8 + 64 && bottom < 8 + 48,
+            "drawn to {}, {}",
+            right,
+            bottom
+        );
+        assert!(bottom > 8 + 20, "second line is missing");
+
+        // The test font has no Japanese, so the second line is the box of missing characters
+        assert_eq!(font.glyph_id('\u{3042}'), ab_glyph::GlyphId(0));
+        assert!(
+            second_line_ink(&original, &annotated) > 20,
+            "second line is not drawn"
+        );
+
+        // Larger text covers more
+        let small = changed_bounds(
+            &original,
+            &annotate(
+                &original,
+                &[label(Point { x: 8.0, y: 8.0 }, "A", 8.0)],
+                Some(&font),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let large = changed_bounds(
+            &original,
+            &annotate(
+                &original,
+                &[label(Point { x: 8.0, y: 8.0 }, "A", 24.0)],
+                Some(&font),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(large.3 - large.1 > (small.3 - small.1) * 2);
+    }
+
+    /// Pixels drawn where the second line of `draws_text_labels` is.
+    fn second_line_ink(original: &image::DynamicImage, annotated: &image::DynamicImage) -> usize {
+        let (original, annotated) = (original.to_rgba8(), annotated.to_rgba8());
+
+        (8..32)
+            .flat_map(|x| (34..56).map(move |y| (x, y)))
+            .filter(|(x, y)| annotated.get_pixel(*x, *y) != original.get_pixel(*x, *y))
+            .count()
+    }
+
+    #[test]
+    fn draws_japanese_with_bundled_font() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(LABEL_FONT_PATH);
+
+        // NOTE: The font is not in the repository. Run `scripts/prepare.js` to test it.
+        if !path.exists() {
+            eprintln!("Skipping, {:?} is missing", path);
+            return;
+        }
+
+        let font = read_font(&path).unwrap();
+        let original = background();
+
+        let annotated = annotate(
+            &original,
+            &[label(Point { x: 8.0, y: 8.0 }, "Hello,\n\u{3042}", 16.0)],
+            Some(&font),
+        )
+        .unwrap();
+
+        // Drawn with its glyph, not the box of missing ones
+        assert_ne!(font.glyph_id('\u{3042}'), ab_glyph::GlyphId(0));
+        assert!(
+            second_line_ink(&original, &annotated) > 20,
+            "second line is not drawn"
+        );
+    }
+
+    #[test]
+    fn draws_text_labels_like_golden() {
+        let annotated = annotate(
+            &background(),
+            &[
+                label(Point { x: 4.0, y: 4.0 }, "Hi!", 20.0),
+                Annotation::Text {
+                    position: Point { x: 40.0, y: 36.0 },
+                    text: "Sent".to_string(),
+                    color: AnnotationColor {
+                        r: 255,
+                        g: 255,
+                        b: 255,
+                        a: 255,
+                    },
+                    size: 14.0,
+                    background: Some(AnnotationColor {
+                        r: 0,
+                        g: 0,
+                        b: 0,
+                        a: 160,
+                    }),
+                },
+            ],
+            Some(&font()),
+        )
+        .unwrap();
+
+        assert_golden("text_labels", &annotated);
+    }
+
+    #[test]
+    fn pads_label_background() {
+        let original = background();
+
+        let annotated = annotate(
+            &original,
+            &[Annotation::Text {
+                position: Point { x: 16.0, y: 16.0 },
+                text: "i".to_string(),
+                color: RED,
+                size: 16.0,
+                background: Some(AnnotationColor {
+                    r: 0,
+                    g: 0,
+                    b: 0,
+                    a: 255,
+                }),
+            }],
+            Some(&font()),
+        )
+        .unwrap();
+
+        let (left, top, _, _) = changed_bounds(&original, &annotated).unwrap();
+        assert_eq!((left, top), (14, 14));
+        assert_eq!(
+            annotated.to_rgba8().get_pixel(14, 14),
+            &image::Rgba([0, 0, 0, 255])
+        );
+    }
+
+    #[test]
+    fn requires_font_for_labels() {
+        assert!(annotate(
+            &background(),
+            &[label(Point { x: 0.0, y: 0.0 }, "name", 8.0)],
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn blurs_only_inside_the_box() {
+        let original = background();
+        let rect = AnnotationRect {
+            x: 16.0,
+            y: 16.0,
+            width: 32.0,
+            height: 24.0,
+        };
+
+        let annotated = annotate(
+            &original,
+            &[
+                Annotation::Freehand {
+                    points: vec![Point { x: 20.0, y: 20.0 }, Point { x: 44.0, y: 36.0 }],
+                    color: RED,
+                    width: 3.0,
+                },
+                Annotation::Blur { rect, radius: 4.0 },
+            ],
+            None,
+        )
+        .unwrap();
+
+        assert_golden("blur_box", &annotated);
+
+        let (original, annotated) = (original.to_rgba8(), annotated.to_rgba8());
+
+        // NOTE: Blurring leaves the smooth gradient inside the checker squares as it is
+        let mut changed = 0;
+
+        for (x, y, pixel) in annotated.enumerate_pixels() {
+            if (16..48).contains(&x) && (16..40).contains(&y) {
+                changed += (pixel != original.get_pixel(x, y)) as u32;
+            } else {
+                assert_eq!(pixel, original.get_pixel(x, y), "pixel at {}, {}", x, y);
+            }
+        }
+
+        assert!(changed > 32 * 24 / 2, "only {} pixels changed", changed);
+    }
+
+    #[test]
+    fn skips_empty_shapes() {
+        let original = background();
+
+        let annotated = annotate(
+            &original,
+            &[
+                Annotation::Arrow {
+                    from: Point { x: 10.0, y: 10.0 },
+                    to: Point { x: 10.0, y: 10.0 },
+                    color: RED,
+                    width: 2.0,
+                },
+                Annotation::Rectangle {
+                    rect: AnnotationRect {
+                        x: 10.0,
+                        y: 10.0,
+                        width: 0.0,
+                        height: 20.0,
+                    },
+                    color: RED,
+                    stroke_width: None,
+                },
+                Annotation::Freehand {
+                    points: Vec::new(),
+                    color: RED,
+                    width: 2.0,
+                },
+                Annotation::Blur {
+                    rect: AnnotationRect {
+                        x: 200.0,
+                        y: 200.0,
+                        width: 10.0,
+                        height: 10.0,
+                    },
+                    radius: 4.0,
+                },
+            ],
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(annotated.to_rgba8(), original.to_rgba8());
+    }
+
+    #[test]
+    fn parses_annotations_from_frontend() {
+        let annotations: Vec<Annotation> = serde_json::from_str(
+            r#"[
+                { "Arrow": { "from": { "x": 1, "y": 2 }, "to": { "x": 3, "y": 4 }, "color": { "r": 255, "g": 0, "b": 0, "a": 255 }, "width": 2 } },
+                { "Blur": { "rect": { "x": 0, "y": 0, "width": 10, "height": 10 }, "radius": 6 } }
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            annotations[1],
+            Annotation::Blur {
+                rect: AnnotationRect {
+                    x: 0.0,
+                    y: 0.0,
+                    width: 10.0,
+                    height: 10.0,
+                },
+                radius: 6.0,
+            }
+        );
+    }
+}
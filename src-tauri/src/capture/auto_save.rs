@@ -0,0 +1,376 @@
+use tauri::Manager as _;
+
+use super::FinishCaptureNextAction;
+use crate::prelude::*;
+
+const DEFAULT_TEMPLATE: &str = "{date}_{time}_{monitor}_{mode}_{counter}";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SaveFormat {
+    Png,
+    Jpeg,
+    WebP,
+}
+
+impl SaveFormat {
+    fn parse(value: Option<&str>) -> Self {
+        match value.map(str::to_ascii_lowercase).as_deref() {
+            Some("jpeg") | Some("jpg") => SaveFormat::Jpeg,
+            Some("webp") => SaveFormat::WebP,
+            _ => SaveFormat::Png,
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            SaveFormat::Png => "png",
+            SaveFormat::Jpeg => "jpg",
+            SaveFormat::WebP => "webp",
+        }
+    }
+
+    fn image_format(self) -> image::ImageFormat {
+        match self {
+            SaveFormat::Png => image::ImageFormat::Png,
+            SaveFormat::Jpeg => image::ImageFormat::Jpeg,
+            SaveFormat::WebP => image::ImageFormat::WebP,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AutoSaveSettings {
+    directory: std::path::PathBuf,
+    template: String,
+    format: SaveFormat,
+    save_full: bool,
+    save_cropped: bool,
+}
+
+/// Keeps copies of a capture in the auto-save folder, named after a template with
+/// `{date}`, `{time}`, `{monitor}`, `{mode}`, `{kind}` (full or cropped) and `{counter}`.
+pub struct AutoSaver {
+    settings: AutoSaveSettings,
+    time: chrono::DateTime<chrono::Local>,
+    monitor: String,
+    mode: &'static str,
+}
+
+impl AutoSaver {
+    /// Returns `None` unless `capture_auto_save_enabled` is set and a folder is configured.
+    pub fn load(
+        app_handle: &tauri::AppHandle,
+        monitor: &str,
+        next_action: &FinishCaptureNextAction,
+    ) -> Option<Self> {
+        let state = app_handle.state::<crate::app_data::AppData>();
+
+        if !state.is_healthy() {
+            return None;
+        }
+
+        let config = state.lock_config();
+
+        if !config.get_bool("capture_auto_save_enabled", false) {
+            return None;
+        }
+
+        let directory = config
+            .get("capture_auto_save_directory")
+            .filter(|directory| !directory.is_empty())?;
+
+        let settings = AutoSaveSettings {
+            directory: std::path::PathBuf::from(directory),
+            template: config
+                .get("capture_auto_save_template")
+                .filter(|template| !template.trim().is_empty())
+                .unwrap_or(DEFAULT_TEMPLATE)
+                .to_string(),
+            format: SaveFormat::parse(config.get("capture_auto_save_format")),
+            save_full: config.get_bool("capture_auto_save_full", false),
+            save_cropped: config.get_bool("capture_auto_save_cropped", true),
+        };
+
+        Some(Self {
+            settings,
+            time: chrono::Local::now(),
+            monitor: monitor.to_string(),
+            mode: mode_name(next_action),
+        })
+    }
+
+    /// Saves the enabled images and returns their paths. `full` is `None` when the image is sent
+    /// uncropped, e.g. a whole window. Failures are only logged, so that a full disk does not
+    /// keep the capture from being sent.
+    pub fn save(
+        &self,
+        full: Option<&image::DynamicImage>,
+        cropped: &image::DynamicImage,
+    ) -> Vec<String> {
+        [
+            (self.settings.save_full, "full", full),
+            (self.settings.save_cropped, "cropped", Some(cropped)),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, kind, image)| image.filter(|_| enabled).map(|image| (kind, image)))
+        .filter_map(|(kind, image)| match self.save_image(image, kind) {
+            Ok(path) => {
+                info!("Saved {} capture to {:?}", kind, path);
+                Some(path.to_string_lossy().into_owned())
+            }
+            Err(e) => {
+                error!("Failed to auto-save {} capture: {}", kind, e);
+                None
+            }
+        })
+        .collect()
+    }
+
+    fn save_image(
+        &self,
+        image: &image::DynamicImage,
+        kind: &str,
+    ) -> Result<std::path::PathBuf, String> {
+        std::fs::create_dir_all(&self.settings.directory)
+            .map_err(|e| format!("Failed to create {:?}: {e}", self.settings.directory))?;
+
+        let path = self.available_path(kind);
+        let format = self.settings.format.image_format();
+
+        // NOTE: JPEG has no alpha channel, e.g. for the gaps of a stitched desktop
+        let result = match self.settings.format {
+            SaveFormat::Jpeg => image.to_rgb8().save_with_format(&path, format),
+            _ => image.save_with_format(&path, format),
+        };
+
+        result.map_err(|e| format!("Failed to save {:?}: {e}", path))?;
+
+        Ok(path)
+    }
+
+    /// Returns the first path that does not exist yet, counting up from 1.
+    /// Without `{counter}` in the template, the count is appended from the second file on.
+    fn available_path(&self, kind: &str) -> std::path::PathBuf {
+        let template = &self.settings.template;
+
+        let mut counter = 1;
+
+        loop {
+            let mut name = render_template(
+                template,
+                &self.time,
+                &self.monitor,
+                self.mode,
+                kind,
+                counter,
+            );
+
+            // NOTE: Keeps the full capture apart from the cropped one when the template cannot
+            if !template.contains("{kind}") && kind == "full" {
+                name.push_str("_full");
+            }
+
+            if !template.contains("{counter}") && counter > 1 {
+                name.push_str(&format!("_{}", counter));
+            }
+
+            let path = self.settings.directory.join(format!(
+                "{}.{}",
+                name,
+                self.settings.format.extension()
+            ));
+
+            if !path.exists() {
+                return path;
+            }
+
+            counter += 1;
+        }
+    }
+}
+
+fn mode_name(next_action: &FinishCaptureNextAction) -> &'static str {
+    match next_action {
+        FinishCaptureNextAction::Ask => "ask",
+        FinishCaptureNextAction::UploadImageToVideoServer => "video",
+        FinishCaptureNextAction::UploadImageToImageServer => "image",
+        FinishCaptureNextAction::UploadImageToVRChatPrint => "print",
+    }
+}
+
+fn render_template(
+    template: &str,
+    time: &chrono::DateTime<chrono::Local>,
+    monitor: &str,
+    mode: &str,
+    kind: &str,
+    counter: u32,
+) -> String {
+    let name = template
+        .replace("{date}", &time.format("%Y-%m-%d").to_string())
+        .replace("{time}", &time.format("%H-%M-%S").to_string())
+        .replace("{monitor}", monitor)
+        .replace("{mode}", mode)
+        .replace("{kind}", kind)
+        .replace("{counter}", &format!("{:03}", counter));
+
+    // Characters that are not allowed in Windows file names, including path separators
+    name.chars()
+        .map(|c| match c {
+            '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone as _;
+
+    use super::*;
+
+    fn saver(directory: &str, template: &str, format: SaveFormat) -> AutoSaver {
+        let directory = crate::file::temp_file_path(directory);
+        let _ = std::fs::remove_dir_all(&directory);
+
+        AutoSaver {
+            settings: AutoSaveSettings {
+                directory,
+                template: template.to_string(),
+                format,
+                save_full: true,
+                save_cropped: true,
+            },
+            time: chrono::Local
+                .with_ymd_and_hms(2026, 10, 19, 21, 5, 9)
+                .unwrap(),
+            monitor: "1".to_string(),
+            mode: mode_name(&FinishCaptureNextAction::UploadImageToVRChatPrint),
+        }
+    }
+
+    fn file_name(path: &str) -> String {
+        std::path::Path::new(path)
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn renders_template() {
+        let saver = saver("auto_save_test_template", DEFAULT_TEMPLATE, SaveFormat::Png);
+
+        assert_eq!(
+            render_template(DEFAULT_TEMPLATE, &saver.time, "1", "print", "cropped", 7),
+            "2026-10-19_21-05-09_1_print_007"
+        );
+        assert_eq!(
+            render_template(
+                "{kind}/{mode}:{monitor}",
+                &saver.time,
+                "desktop",
+                "ask",
+                "full",
+                1
+            ),
+            "full_ask_desktop"
+        );
+    }
+
+    #[test]
+    fn counts_up_instead_of_overwriting() {
+        let saver = saver(
+            "auto_save_test_counter",
+            "capture_{counter}",
+            SaveFormat::Png,
+        );
+        let image = image::DynamicImage::new_rgba8(4, 4);
+
+        let first = saver.save(Some(&image), &image);
+        let second = saver.save(Some(&image), &image);
+
+        assert_eq!(
+            [&first[..], &second[..]]
+                .concat()
+                .iter()
+                .map(|path| file_name(path))
+                .collect::<Vec<_>>(),
+            [
+                "capture_001_full.png",
+                "capture_001.png",
+                "capture_002_full.png",
+                "capture_002.png"
+            ]
+        );
+
+        let saver = AutoSaver {
+            settings: AutoSaveSettings {
+                template: "capture".to_string(),
+                save_full: false,
+                ..saver.settings
+            },
+            ..saver
+        };
+
+        assert_eq!(
+            file_name(&saver.save(Some(&image), &image)[0]),
+            "capture.png"
+        );
+        assert_eq!(
+            file_name(&saver.save(Some(&image), &image)[0]),
+            "capture_2.png"
+        );
+    }
+
+    #[test]
+    fn saves_uncropped_image_once() {
+        let saver = saver("auto_save_test_uncropped", "{kind}", SaveFormat::Png);
+        let image = image::DynamicImage::new_rgba8(4, 4);
+
+        let saved = saver.save(None, &image);
+
+        assert_eq!(
+            saved.iter().map(|path| file_name(path)).collect::<Vec<_>>(),
+            ["cropped.png"]
+        );
+    }
+
+    #[test]
+    fn saves_in_the_chosen_format() {
+        let image = image::DynamicImage::new_rgba8(4, 4);
+
+        for (format, extension, image_format) in [
+            (SaveFormat::Jpeg, "jpg", image::ImageFormat::Jpeg),
+            (SaveFormat::WebP, "webp", image::ImageFormat::WebP),
+        ] {
+            let saver = saver(
+                &format!("auto_save_test_{}", extension),
+                "{mode}_{kind}",
+                format,
+            );
+
+            let saved = saver.save(Some(&image), &image);
+
+            assert_eq!(saved.len(), 2);
+            assert_eq!(file_name(&saved[1]), format!("print_cropped.{}", extension));
+            assert_eq!(
+                image::ImageReader::open(&saved[1])
+                    .unwrap()
+                    .with_guessed_format()
+                    .unwrap()
+                    .format(),
+                Some(image_format)
+            );
+        }
+    }
+
+    #[test]
+    fn parses_format() {
+        assert_eq!(SaveFormat::parse(Some("JPG")), SaveFormat::Jpeg);
+        assert_eq!(SaveFormat::parse(Some("webp")), SaveFormat::WebP);
+        assert_eq!(SaveFormat::parse(Some("gif")), SaveFormat::Png);
+        assert_eq!(SaveFormat::parse(None), SaveFormat::Png);
+    }
+}
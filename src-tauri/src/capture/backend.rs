@@ -0,0 +1,217 @@
+/// Rectangle in virtual desktop pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Bounds {
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x
+            && y >= self.y
+            && (x as i64) < self.x as i64 + self.width as i64
+            && (y as i64) < self.y as i64 + self.height as i64
+    }
+
+    pub fn center(&self) -> (i32, i32) {
+        (
+            self.x + (self.width / 2) as i32,
+            self.y + (self.height / 2) as i32,
+        )
+    }
+
+    /// Returns the smallest rectangle containing both.
+    pub fn union(&self, other: &Bounds) -> Bounds {
+        let x1 = self.x.min(other.x);
+        let y1 = self.y.min(other.y);
+        let x2 = (self.x as i64 + self.width as i64).max(other.x as i64 + other.width as i64);
+        let y2 = (self.y as i64 + self.height as i64).max(other.y as i64 + other.height as i64);
+
+        Bounds {
+            x: x1,
+            y: y1,
+            width: (x2 - x1 as i64) as u32,
+            height: (y2 - y1 as i64) as u32,
+        }
+    }
+
+    /// Returns the overlapping part, or `None` if the rectangles do not overlap.
+    pub fn intersection(&self, other: &Bounds) -> Option<Bounds> {
+        let x1 = self.x.max(other.x);
+        let y1 = self.y.max(other.y);
+        let x2 = (self.x as i64 + self.width as i64).min(other.x as i64 + other.width as i64);
+        let y2 = (self.y as i64 + self.height as i64).min(other.y as i64 + other.height as i64);
+
+        if x2 <= x1 as i64 || y2 <= y1 as i64 {
+            return None;
+        }
+
+        Some(Bounds {
+            x: x1,
+            y: y1,
+            width: (x2 - x1 as i64) as u32,
+            height: (y2 - y1 as i64) as u32,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorInfo {
+    /// Index in the order the backend enumerates monitors.
+    pub index: usize,
+    pub name: String,
+    pub bounds: Bounds,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowInfo {
+    /// Native handle (HWND on Windows, XID on X11).
+    pub id: u64,
+    pub title: String,
+    pub process_name: String,
+    pub bounds: Bounds,
+    /// Height of the title bar at the top of `bounds`, 0 if the bounds exclude decorations.
+    pub title_bar_height: u32,
+}
+
+/// Platform screen capture. Implementations are created per capture and used from the capture thread only.
+pub trait CaptureBackend: Send {
+    fn monitors(&self) -> Result<Vec<MonitorInfo>, String>;
+
+    fn capture_monitor(&self, monitor: &MonitorInfo) -> Result<image::RgbaImage, String>;
+
+    fn windows(&self) -> Result<Vec<WindowInfo>, String>;
+
+    /// Crops the window out of its monitor by default, so it must not be covered by other windows.
+    fn capture_window(&self, window: &WindowInfo) -> Result<image::RgbaImage, String> {
+        let monitors = self.monitors()?;
+
+        let monitor = monitor_of_window(&monitors, window)
+            .ok_or(format!("Window {} is not on any monitor", window.id))?;
+
+        crop_window_from_monitor(&self.capture_monitor(monitor)?, monitor, window)
+    }
+}
+
+/// Crops the part of `window` that is on `monitor` out of a capture of that monitor.
+pub fn crop_window_from_monitor(
+    monitor_image: &image::RgbaImage,
+    monitor: &MonitorInfo,
+    window: &WindowInfo,
+) -> Result<image::RgbaImage, String> {
+    let visible = window.bounds.intersection(&monitor.bounds).ok_or(format!(
+        "Window {} is not on monitor {}",
+        window.id, monitor.index
+    ))?;
+
+    Ok(image::imageops::crop_imm(
+        monitor_image,
+        (visible.x - monitor.bounds.x) as u32,
+        (visible.y - monitor.bounds.y) as u32,
+        visible.width,
+        visible.height,
+    )
+    .to_image())
+}
+
+/// Returns the monitor showing the center of `window`, or the first one it overlaps.
+pub fn monitor_of_window<'a>(
+    monitors: &'a [MonitorInfo],
+    window: &WindowInfo,
+) -> Option<&'a MonitorInfo> {
+    let (x, y) = window.bounds.center();
+
+    monitors
+        .iter()
+        .find(|monitor| monitor.bounds.contains(x, y))
+        .or_else(|| {
+            monitors
+                .iter()
+                .find(|monitor| monitor.bounds.intersection(&window.bounds).is_some())
+        })
+}
+
+#[cfg(windows)]
+pub fn default_backend() -> Result<Box<dyn CaptureBackend>, String> {
+    Ok(Box::new(super::dxgi::DxgiBackend))
+}
+
+#[cfg(target_os = "linux")]
+pub fn default_backend() -> Result<Box<dyn CaptureBackend>, String> {
+    Ok(Box::new(super::x11::X11Backend::connect()?))
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+pub fn default_backend() -> Result<Box<dyn CaptureBackend>, String> {
+    Err("Screen capture is not supported on this platform".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds(x: i32, y: i32, width: u32, height: u32) -> Bounds {
+        Bounds {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn intersects_bounds() {
+        assert_eq!(
+            bounds(0, 0, 100, 100).intersection(&bounds(50, -20, 100, 100)),
+            Some(bounds(50, 0, 50, 80))
+        );
+        assert_eq!(
+            bounds(0, 0, 100, 100).intersection(&bounds(100, 0, 10, 10)),
+            None
+        );
+    }
+
+    #[test]
+    fn unites_bounds() {
+        assert_eq!(
+            bounds(-100, 20, 100, 50).union(&bounds(0, 0, 200, 40)),
+            bounds(-100, 0, 300, 70)
+        );
+    }
+
+    #[test]
+    fn finds_monitor_of_window() {
+        let monitors = [
+            MonitorInfo {
+                index: 0,
+                name: "left".to_string(),
+                bounds: bounds(-1920, 0, 1920, 1080),
+            },
+            MonitorInfo {
+                index: 1,
+                name: "right".to_string(),
+                bounds: bounds(0, 0, 1920, 1080),
+            },
+        ];
+
+        let window = |x| WindowInfo {
+            id: 1,
+            title: "VRChat".to_string(),
+            process_name: "VRChat.exe".to_string(),
+            bounds: bounds(x, 100, 800, 600),
+            title_bar_height: 0,
+        };
+
+        assert_eq!(
+            monitor_of_window(&monitors, &window(-900)).unwrap().index,
+            0
+        );
+        assert_eq!(
+            monitor_of_window(&monitors, &window(-300)).unwrap().index,
+            1
+        );
+        assert!(monitor_of_window(&monitors, &window(5000)).is_none());
+    }
+}
@@ -0,0 +1,140 @@
+use super::backend::Bounds;
+
+/// Monitor captures stitched together in virtual desktop pixels.
+/// Areas between monitors that no monitor shows are transparent.
+pub struct VirtualDesktop {
+    pub bounds: Bounds,
+    pub image: image::RgbaImage,
+}
+
+impl VirtualDesktop {
+    pub fn stitch(captures: &[(Bounds, image::RgbaImage)]) -> Result<Self, String> {
+        let bounds = captures
+            .iter()
+            .map(|(bounds, _)| *bounds)
+            .reduce(|a, b| a.union(&b))
+            .ok_or("No monitor was captured".to_string())?;
+
+        let mut image = image::RgbaImage::new(bounds.width, bounds.height);
+
+        for (monitor, capture) in captures {
+            // NOTE: A capture may not be in desktop pixels, e.g. when a backend reports scaled bounds.
+            let resized;
+            let capture = if capture.dimensions() == (monitor.width, monitor.height) {
+                capture
+            } else {
+                resized = image::imageops::resize(
+                    capture,
+                    monitor.width,
+                    monitor.height,
+                    image::imageops::FilterType::Triangle,
+                );
+                &resized
+            };
+
+            image::imageops::replace(
+                &mut image,
+                capture,
+                monitor.x as i64 - bounds.x as i64,
+                monitor.y as i64 - bounds.y as i64,
+            );
+        }
+
+        Ok(Self { bounds, image })
+    }
+
+    /// Crops a selection in virtual desktop pixels. Parts outside the desktop are cut off.
+    pub fn crop(&self, selection: &Bounds) -> Result<image::RgbaImage, String> {
+        let visible = selection
+            .intersection(&self.bounds)
+            .ok_or("Selection is outside the desktop".to_string())?;
+
+        Ok(image::imageops::crop_imm(
+            &self.image,
+            (visible.x as i64 - self.bounds.x as i64) as u32,
+            (visible.y as i64 - self.bounds.y as i64) as u32,
+            visible.width,
+            visible.height,
+        )
+        .to_image())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: image::Rgba<u8> = image::Rgba([255, 0, 0, 255]);
+    const GREEN: image::Rgba<u8> = image::Rgba([0, 255, 0, 255]);
+    const TRANSPARENT: image::Rgba<u8> = image::Rgba([0, 0, 0, 0]);
+
+    fn bounds(x: i32, y: i32, width: u32, height: u32) -> Bounds {
+        Bounds {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    fn filled(width: u32, height: u32, color: image::Rgba<u8>) -> image::RgbaImage {
+        image::RgbaImage::from_pixel(width, height, color)
+    }
+
+    #[test]
+    fn stitches_monitors_with_gaps_and_different_resolutions() {
+        // A 40x20 monitor left of the primary one, and a taller 20x40 one 10 pixels right of it
+        let desktop = VirtualDesktop::stitch(&[
+            (bounds(0, 0, 20, 20), filled(20, 20, RED)),
+            (bounds(-40, 5, 40, 20), filled(40, 20, GREEN)),
+            (bounds(30, -10, 20, 40), filled(20, 40, RED)),
+        ])
+        .unwrap();
+
+        assert_eq!(desktop.bounds, bounds(-40, -10, 90, 40));
+        assert_eq!(desktop.image.dimensions(), (90, 40));
+
+        assert_eq!(desktop.image.get_pixel(0, 15), &GREEN);
+        assert_eq!(desktop.image.get_pixel(40, 10), &RED);
+        // Gap between the primary and the right monitor
+        assert_eq!(desktop.image.get_pixel(65, 15), &TRANSPARENT);
+        // Above the left monitor
+        assert_eq!(desktop.image.get_pixel(0, 0), &TRANSPARENT);
+    }
+
+    #[test]
+    fn scales_captures_to_monitor_bounds() {
+        let desktop =
+            VirtualDesktop::stitch(&[(bounds(0, 0, 10, 10), filled(20, 20, GREEN))]).unwrap();
+
+        assert_eq!(desktop.image.dimensions(), (10, 10));
+        assert!(desktop.image.pixels().all(|pixel| pixel == &GREEN));
+    }
+
+    #[test]
+    fn crops_selection_spanning_monitors() {
+        let desktop = VirtualDesktop::stitch(&[
+            (bounds(0, 0, 20, 20), filled(20, 20, RED)),
+            (bounds(-20, 0, 20, 10), filled(20, 10, GREEN)),
+        ])
+        .unwrap();
+
+        let cropped = desktop.crop(&bounds(-10, 5, 20, 10)).unwrap();
+
+        assert_eq!(cropped.dimensions(), (20, 10));
+        assert_eq!(cropped.get_pixel(0, 0), &GREEN);
+        assert_eq!(cropped.get_pixel(15, 0), &RED);
+        // Below the shorter left monitor
+        assert_eq!(cropped.get_pixel(0, 9), &TRANSPARENT);
+
+        // Cut off at the desktop edge
+        assert_eq!(
+            desktop
+                .crop(&bounds(10, 10, 100, 100))
+                .unwrap()
+                .dimensions(),
+            (10, 10)
+        );
+        assert!(desktop.crop(&bounds(100, 100, 10, 10)).is_err());
+    }
+}
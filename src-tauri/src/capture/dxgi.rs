@@ -0,0 +1,113 @@
+use windows::Win32::Graphics::Gdi::{GetMonitorInfoW, HMONITOR, MONITORINFO};
+
+use super::backend::{Bounds, CaptureBackend, MonitorInfo, WindowInfo};
+use crate::prelude::*;
+
+/// Captures monitors with DXGI Desktop Duplication.
+pub struct DxgiBackend;
+
+impl DxgiBackend {
+    fn monitor_bounds(monitor: &windows_capture::monitor::Monitor) -> Result<Bounds, String> {
+        let mut info = MONITORINFO {
+            cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+            ..Default::default()
+        };
+
+        let succeeded =
+            unsafe { GetMonitorInfoW(HMONITOR(monitor.as_raw_hmonitor()), &mut info) }.as_bool();
+
+        if !succeeded {
+            return Err("Failed to get monitor info".to_string());
+        }
+
+        Ok(rect_to_bounds(&info.rcMonitor))
+    }
+}
+
+fn rect_to_bounds(rect: &windows::Win32::Foundation::RECT) -> Bounds {
+    Bounds {
+        x: rect.left,
+        y: rect.top,
+        width: (rect.right - rect.left).max(0) as u32,
+        height: (rect.bottom - rect.top).max(0) as u32,
+    }
+}
+
+impl CaptureBackend for DxgiBackend {
+    fn monitors(&self) -> Result<Vec<MonitorInfo>, String> {
+        let monitors = windows_capture::monitor::Monitor::enumerate()
+            .map_err(|e| format!("Failed to enumerate monitors: {e}"))?;
+
+        monitors
+            .iter()
+            .enumerate()
+            .map(|(index, monitor)| {
+                Ok(MonitorInfo {
+                    index,
+                    name: monitor
+                        .name()
+                        .map_err(|e| format!("Monitor {} has no name: {:?}", index, e))?,
+                    bounds: Self::monitor_bounds(monitor)?,
+                })
+            })
+            .collect()
+    }
+
+    fn capture_monitor(&self, monitor: &MonitorInfo) -> Result<image::RgbaImage, String> {
+        let monitors = windows_capture::monitor::Monitor::enumerate()
+            .map_err(|e| format!("Failed to enumerate monitors: {e}"))?;
+
+        let dxgi_monitor = monitors
+            .get(monitor.index)
+            .ok_or(format!("Monitor {} is gone", monitor.index))?;
+
+        // Create a duplication session for this monitor
+        let mut dup = windows_capture::dxgi_duplication_api::DxgiDuplicationApi::new(*dxgi_monitor)
+            .map_err(|e| format!("Failed to duplicate monitor {}: {e}", monitor.index))?;
+
+        // NOTE: The frame is converted by windows-capture, which only writes image files.
+        let path = crate::file::temp_file_path(&format!("dxgi_capture_{}.png", monitor.index));
+
+        for i in 0..10 {
+            let mut frame = dup
+                .acquire_next_frame(1000)
+                .map_err(|e| format!("Failed to acquire frame: {e}"))?;
+
+            // Note: The API could send an empty frame especially
+            // in the first few calls, you can check this by seeing if
+            // frame.frame_info().LastPresentTime is zero.
+            if frame.frame_info().LastPresentTime == 0 {
+                info!("Frame {} is empty", i);
+                continue;
+            }
+
+            frame
+                .save_as_image(&path, windows_capture::encoder::ImageFormat::Png)
+                .map_err(|e| format!("Failed to save frame: {e}"))?;
+
+            return image::open(&path)
+                .map(|image| image.to_rgba8())
+                .map_err(|e| format!("Failed to read captured frame: {e}"));
+        }
+
+        Err("No frame captured".to_string())
+    }
+
+    fn windows(&self) -> Result<Vec<WindowInfo>, String> {
+        let windows = windows_capture::window::Window::enumerate()
+            .map_err(|e| format!("Failed to enumerate windows: {e}"))?;
+
+        Ok(windows
+            .iter()
+            .filter_map(|window| {
+                Some(WindowInfo {
+                    id: window.as_raw_hwnd() as usize as u64,
+                    title: window.title().ok()?,
+                    process_name: window.process_name().unwrap_or_default(),
+                    bounds: rect_to_bounds(&window.rect().ok()?),
+                    title_bar_height: window.title_bar_height().unwrap_or(0),
+                })
+            })
+            .collect())
+    }
+}
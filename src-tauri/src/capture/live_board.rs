@@ -0,0 +1,650 @@
+use tauri::Manager as _;
+use tauri_specta::Event as _;
+
+use super::backend::CaptureBackend;
+use crate::prelude::*;
+
+const DEFAULT_PORT: u16 = 17381;
+const MIN_INTERVAL_SECONDS: u32 = 1;
+const MAX_INTERVAL_SECONDS: u32 = 60 * 60;
+const RECEIVE_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// Frames are compared at this size, so capture noise does not count as a change.
+const SIGNATURE_SIZE: u32 = 64;
+/// Smallest brightness difference of a signature pixel that counts as a change.
+const CHANGE_THRESHOLD: u8 = 8;
+
+static LIVE_BOARD: std::sync::Mutex<Option<LiveBoardThread>> = std::sync::Mutex::new(None);
+
+struct LiveBoardThread {
+    stop: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    thread: std::thread::JoinHandle<()>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, specta::Type)]
+pub enum LiveBoardSource {
+    /// The region selected in the last capture.
+    LastRegion,
+    Window {
+        window_id: String,
+        exclude_title_bar: bool,
+    },
+}
+
+enum Target {
+    Region(super::LastRegion),
+    Window { id: u64, exclude_title_bar: bool },
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, specta::Type, tauri_specta::Event)]
+pub struct LiveBoardStatusEvent {
+    running: bool,
+    url: Option<String>,
+    published_frames: u32,
+    skipped_frames: u32,
+    /// Error of the last capture, cleared by the next successful one.
+    error: Option<String>,
+}
+
+/// Re-captures `source` every `interval_seconds` and serves the latest frame on the LAN as a video,
+/// since only video players in VRChat load arbitrary URLs. Returns the URL, which stays the same
+/// while the board runs, for video players to refresh.
+#[tauri::command]
+#[specta::specta]
+pub async fn start_live_board(
+    app_handle: tauri::AppHandle,
+    source: LiveBoardSource,
+    interval_seconds: u32,
+) -> Result<String, String> {
+    // NOTE: Stopping the previous board waits for its thread, which must not block the event loop.
+    tauri::async_runtime::spawn_blocking(move || start(app_handle, source, interval_seconds))
+        .await
+        .map_err(|e| format!("Failed to start live board: {e}"))?
+}
+
+fn start(
+    app_handle: tauri::AppHandle,
+    source: LiveBoardSource,
+    interval_seconds: u32,
+) -> Result<String, String> {
+    // NOTE: Held until the new board is stored, so two starts cannot race for the port.
+    let mut current = LIVE_BOARD.lock().unwrap();
+
+    stop_thread(current.take());
+
+    let target = match source {
+        LiveBoardSource::LastRegion => Target::Region(
+            super::LAST_REGION
+                .lock()
+                .unwrap()
+                .clone()
+                .ok_or("Select a region with a capture first".to_string())?,
+        ),
+        LiveBoardSource::Window {
+            window_id,
+            exclude_title_bar,
+        } => Target::Window {
+            id: window_id
+                .parse()
+                .map_err(|e| format!("Invalid window id {}: {}", window_id, e))?,
+            exclude_title_bar,
+        },
+    };
+
+    let interval = std::time::Duration::from_secs(
+        interval_seconds.clamp(MIN_INTERVAL_SECONDS, MAX_INTERVAL_SECONDS) as u64,
+    );
+
+    let (bind_address, port) = {
+        let state = app_handle.state::<crate::app_data::AppData>();
+
+        if !state.is_healthy() {
+            return Err("Config is not loaded".to_string());
+        }
+
+        let config = state.lock_config();
+
+        let bind_address = config
+            .get("live_board_bind_address")
+            .and_then(|address| address.parse().ok());
+
+        let port = config
+            .get("live_board_port")
+            .and_then(|port| port.parse().ok())
+            .unwrap_or(DEFAULT_PORT);
+
+        (bind_address, port)
+    };
+
+    // NOTE: Only listens on the LAN interface unless configured otherwise. The path is random to keep the screen private.
+    let bind_address = bind_address.unwrap_or_else(lan_address);
+
+    let server = tiny_http::Server::http((bind_address, port)).map_err(|e| {
+        format!(
+            "Failed to start live board on {}: {}",
+            std::net::SocketAddr::new(bind_address, port),
+            e
+        )
+    })?;
+
+    let url_address = if bind_address.is_unspecified() {
+        lan_address()
+    } else {
+        bind_address
+    };
+
+    let token = crate::control_api::generate_token().map_err(|e| e.to_string())?;
+    let path = format!("/live/{}.mp4", token);
+    let url = format!(
+        "http://{}{}",
+        std::net::SocketAddr::new(url_address, port),
+        path
+    );
+
+    info!("Live board is published at {}", url);
+
+    let stop = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+    let thread = {
+        let stop = stop.clone();
+        let url = url.clone();
+
+        std::thread::spawn(move || run(&app_handle, server, &stop, &target, &path, url, interval))
+    };
+
+    *current = Some(LiveBoardThread { stop, thread });
+
+    Ok(url)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_live_board() {
+    tauri::async_runtime::spawn_blocking(|| {
+        // NOTE: Held until the thread exits, so a start cannot bind the port before it is released.
+        let mut current = LIVE_BOARD.lock().unwrap();
+
+        stop_thread(current.take());
+    })
+    .await
+    .unwrap_or_else(|e| error!("Failed to stop live board: {:?}", e));
+}
+
+fn stop_thread(previous: Option<LiveBoardThread>) {
+    // NOTE: Wait for the server to release the port, so the board can be started again right away.
+    if let Some(previous) = previous {
+        previous
+            .stop
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+        previous
+            .thread
+            .join()
+            .unwrap_or_else(|_| error!("Live board thread panicked"));
+    }
+}
+
+fn run(
+    app_handle: &tauri::AppHandle,
+    server: tiny_http::Server,
+    stop: &std::sync::atomic::AtomicBool,
+    target: &Target,
+    path: &str,
+    url: String,
+    interval: std::time::Duration,
+) {
+    let mut status = LiveBoardStatusEvent {
+        running: true,
+        url: Some(url),
+        ..Default::default()
+    };
+
+    let emit = |status: &LiveBoardStatusEvent| {
+        status
+            .emit(app_handle)
+            .unwrap_or_else(|e| debug!("Failed to emit live board status: {:?}", e));
+    };
+
+    let setup = super::backend::default_backend().and_then(|backend| {
+        crate::image_to_video::resolve_ffmpeg_path(app_handle)
+            .map(|ffmpeg_path| (backend, ffmpeg_path))
+            .map_err(|e| e.to_string())
+    });
+
+    let (backend, ffmpeg_path) = match setup {
+        Ok(setup) => setup,
+        Err(e) => {
+            error!("Failed to start live board: {}", e);
+            status.running = false;
+            status.error = Some(e);
+            emit(&status);
+            return;
+        }
+    };
+
+    let mut board = LiveBoard::default();
+    let mut next_capture = std::time::Instant::now();
+
+    while !stop.load(std::sync::atomic::Ordering::SeqCst) {
+        if std::time::Instant::now() >= next_capture {
+            next_capture = std::time::Instant::now() + interval;
+
+            // NOTE: Requests wait while a frame is encoded, players retry on their next refresh.
+            match capture_target(backend.as_ref(), target)
+                .and_then(|frame| board.update(&frame, |frame| encode_frame(&ffmpeg_path, frame)))
+            {
+                Ok(true) => {
+                    status.published_frames += 1;
+                    status.error = None;
+                }
+                Ok(false) => {
+                    status.skipped_frames += 1;
+                    status.error = None;
+                }
+                Err(e) => {
+                    info!("Failed to capture live board frame: {}", e);
+                    status.error = Some(e);
+                }
+            }
+
+            emit(&status);
+        }
+
+        let request = match server.recv_timeout(RECEIVE_TIMEOUT) {
+            Ok(Some(request)) => request,
+            Ok(None) => continue,
+            Err(e) => {
+                error!("Live board server failed: {:?}", e);
+                status.error = Some(format!("Server failed: {e}"));
+                break;
+            }
+        };
+
+        let range = request
+            .headers()
+            .iter()
+            .find(|header| header.field.equiv("Range"))
+            .map(|header| header.value.as_str().to_string());
+
+        let response = match route(
+            request.method(),
+            request.url(),
+            path,
+            range.as_deref(),
+            board.video(),
+        ) {
+            Ok(reply) => {
+                let response = tiny_http::Response::from_data(reply.bytes.to_vec())
+                    .with_status_code(reply.status)
+                    .with_header(
+                        tiny_http::Header::from_bytes("Content-Type", "video/mp4")
+                            .expect("Content-Type header is valid"),
+                    )
+                    .with_header(
+                        tiny_http::Header::from_bytes("Accept-Ranges", "bytes")
+                            .expect("Accept-Ranges header is valid"),
+                    )
+                    .with_header(
+                        tiny_http::Header::from_bytes("Cache-Control", "no-store")
+                            .expect("Cache-Control header is valid"),
+                    );
+
+                match reply.content_range {
+                    Some(content_range) => response.with_header(
+                        tiny_http::Header::from_bytes("Content-Range", content_range)
+                            .expect("Content-Range header is valid"),
+                    ),
+                    None => response,
+                }
+            }
+            Err(status) => tiny_http::Response::from_data(Vec::new()).with_status_code(status),
+        };
+
+        request
+            .respond(response)
+            .unwrap_or_else(|e| debug!("Failed to respond to live board request: {:?}", e));
+    }
+
+    status.running = false;
+    emit(&status);
+}
+
+fn capture_target(
+    backend: &dyn CaptureBackend,
+    target: &Target,
+) -> Result<image::RgbaImage, String> {
+    match target {
+        Target::Region(region) => super::capture_overlay_monitor(backend, &region.overlay)
+            .map(|full| super::crop_image(&full, &region.rect, Some(&region.overlay)).to_rgba8()),
+        Target::Window {
+            id,
+            exclude_title_bar,
+        } => {
+            let windows = backend.windows()?;
+            let window = super::window::find_window(&windows, *id)
+                .ok_or(format!("Window {} is gone", id))?;
+
+            super::window::capture_window_image(backend, window, *exclude_title_bar)
+        }
+    }
+}
+
+struct Reply<'a> {
+    status: u16,
+    bytes: &'a [u8],
+    content_range: Option<String>,
+}
+
+/// Returns the part of the video to serve for a request, or the status code to reply with.
+fn route<'a>(
+    method: &tiny_http::Method,
+    url: &str,
+    path: &str,
+    range: Option<&str>,
+    video: Option<&'a [u8]>,
+) -> Result<Reply<'a>, u16> {
+    // Players may append a query string to bypass their own cache
+    let request_path = url.split_once('?').map_or(url, |(path, _)| path);
+
+    if !matches!(method, tiny_http::Method::Get | tiny_http::Method::Head) || request_path != path {
+        return Err(404);
+    }
+
+    let Some(video) = video else {
+        return Err(503);
+    };
+
+    match range.and_then(|range| parse_range(range, video.len())) {
+        Some(Ok(range)) => Ok(Reply {
+            status: 206,
+            content_range: Some(format!(
+                "bytes {}-{}/{}",
+                range.start,
+                range.end - 1,
+                video.len()
+            )),
+            bytes: &video[range],
+        }),
+        Some(Err(())) => Err(416),
+        None => Ok(Reply {
+            status: 200,
+            bytes: video,
+            content_range: None,
+        }),
+    }
+}
+
+/// Parses a single `Range: bytes=` header, as video players seek with it.
+/// Returns `None` for headers to ignore by serving the whole video.
+fn parse_range(header: &str, length: usize) -> Option<Result<std::ops::Range<usize>, ()>> {
+    let (start, end) = header.strip_prefix("bytes=")?.split_once('-')?;
+
+    // NOTE: Several ranges are rare, so they get the whole video instead.
+    if end.contains(',') {
+        return None;
+    }
+
+    let range = match (start.trim(), end.trim()) {
+        ("", suffix) => {
+            let suffix: usize = suffix.parse().ok()?;
+            length.saturating_sub(suffix)..length
+        }
+        (start, "") => start.parse().ok()?..length,
+        (start, end) => {
+            let end: usize = end.parse().ok()?;
+            start.parse().ok()?..end.saturating_add(1).min(length)
+        }
+    };
+
+    Some(if range.start < range.end {
+        Ok(range)
+    } else {
+        Err(())
+    })
+}
+
+/// Address other devices on the LAN can reach this PC at, falling back to loopback.
+fn lan_address() -> std::net::IpAddr {
+    // NOTE: Connecting a UDP socket sends nothing, it only picks the interface of the default route.
+    std::net::UdpSocket::bind(("0.0.0.0", 0))
+        .and_then(|socket| {
+            socket.connect(("10.255.255.255", 1))?;
+            socket.local_addr()
+        })
+        .map(|address| address.ip())
+        .ok()
+        .filter(|ip| !ip.is_unspecified())
+        .unwrap_or(std::net::IpAddr::V4(std::net::Ipv4Addr::LOCALHOST))
+}
+
+/// Encodes a frame the same way as uploads to the video server.
+fn encode_frame(ffmpeg_path: &str, frame: &image::RgbaImage) -> Result<Vec<u8>, String> {
+    let workspace = crate::jobs::create_workspace().map_err(|e| e.to_string())?;
+
+    let frame_path = workspace.join("frame.png");
+    let letterboxed_path = workspace.join("letterboxed.png");
+    let video_path = workspace.join("frame.mp4");
+
+    let video = frame
+        .save(&frame_path)
+        .map_err(|e| format!("Failed to save frame: {e}"))
+        .and_then(|_| {
+            crate::image_to_video::render_video_frame(
+                &frame_path.to_string_lossy(),
+                &letterboxed_path.to_string_lossy(),
+            )
+            .map_err(|e| e.to_string())
+        })
+        .and_then(|_| {
+            tauri::async_runtime::block_on(crate::image_to_video::encode_image_to_video(
+                ffmpeg_path,
+                &letterboxed_path.to_string_lossy(),
+                &video_path.to_string_lossy(),
+            ))
+            .map_err(|e| e.to_string())
+        })
+        .and_then(|_| {
+            std::fs::read(&video_path).map_err(|e| format!("Failed to read encoded frame: {e}"))
+        });
+
+    std::fs::remove_dir_all(&workspace)
+        .unwrap_or_else(|e| debug!("Failed to remove live board workspace: {:?}", e));
+
+    video
+}
+
+/// Latest published frame. Frames that look the same are skipped, so players are not
+/// made to reload a video that did not change.
+#[derive(Default)]
+struct LiveBoard {
+    signature: Option<FrameSignature>,
+    video: Option<Vec<u8>>,
+}
+
+impl LiveBoard {
+    /// Returns whether the frame differs from the published one and replaced it with `encode`d one.
+    fn update(
+        &mut self,
+        frame: &image::RgbaImage,
+        encode: impl FnOnce(&image::RgbaImage) -> Result<Vec<u8>, String>,
+    ) -> Result<bool, String> {
+        let signature = FrameSignature::new(frame);
+
+        if self
+            .signature
+            .as_ref()
+            .is_some_and(|published| !published.differs_from(&signature))
+        {
+            return Ok(false);
+        }
+
+        let video = encode(frame)?;
+
+        self.signature = Some(signature);
+        self.video = Some(video);
+
+        Ok(true)
+    }
+
+    fn video(&self) -> Option<&[u8]> {
+        self.video.as_deref()
+    }
+}
+
+/// Small grayscale thumbnail for a perceptual comparison of frames.
+struct FrameSignature {
+    dimensions: (u32, u32),
+    thumbnail: image::GrayImage,
+}
+
+impl FrameSignature {
+    fn new(frame: &image::RgbaImage) -> Self {
+        Self {
+            dimensions: frame.dimensions(),
+            thumbnail: image::imageops::resize(
+                &image::imageops::grayscale(frame),
+                SIGNATURE_SIZE,
+                SIGNATURE_SIZE,
+                image::imageops::FilterType::Triangle,
+            ),
+        }
+    }
+
+    fn differs_from(&self, other: &FrameSignature) -> bool {
+        self.dimensions != other.dimensions
+            || self
+                .thumbnail
+                .pixels()
+                .zip(other.thumbnail.pixels())
+                .any(|(a, b)| a[0].abs_diff(b[0]) >= CHANGE_THRESHOLD)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(noise: u8, changed_line: bool) -> image::RgbaImage {
+        image::RgbaImage::from_fn(400, 200, |x, y| {
+            if changed_line && (100..140).contains(&x) && (50..60).contains(&y) {
+                return image::Rgba([255, 255, 255, 255]);
+            }
+
+            let value = 40 + ((x + y) % 2) as u8 * noise;
+            image::Rgba([value, value, value, 255])
+        })
+    }
+
+    fn encode(frame: &image::RgbaImage) -> Result<Vec<u8>, String> {
+        crate::clipboard::encode_rgba_as_png(frame.as_raw(), frame.width(), frame.height())
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn skips_unchanged_frames() {
+        let mut board = LiveBoard::default();
+
+        assert!(board.update(&frame(0, false), encode).unwrap());
+        let published = board.video().unwrap().to_vec();
+
+        // Same frame, and the same frame with noise
+        assert!(!board.update(&frame(0, false), encode).unwrap());
+        assert!(!board.update(&frame(3, false), encode).unwrap());
+        assert_eq!(board.video().unwrap(), published);
+
+        // A new line of text
+        assert!(board.update(&frame(0, true), encode).unwrap());
+        assert_ne!(board.video().unwrap(), published);
+
+        // Resized window
+        assert!(board
+            .update(
+                &image::RgbaImage::from_pixel(200, 200, image::Rgba([40, 40, 40, 255])),
+                encode
+            )
+            .unwrap());
+    }
+
+    #[test]
+    fn keeps_published_frame_when_encoding_fails() {
+        let mut board = LiveBoard::default();
+
+        assert!(board.update(&frame(0, false), encode).unwrap());
+        let published = board.video().unwrap().to_vec();
+
+        assert!(board
+            .update(&frame(0, true), |_| Err("ffmpeg failed".to_string()))
+            .is_err());
+        assert_eq!(board.video().unwrap(), published);
+
+        // The failed frame is still new
+        assert!(board.update(&frame(0, true), encode).unwrap());
+    }
+
+    #[test]
+    fn serves_latest_frame_at_the_stable_path() {
+        let path = "/live/abc.mp4";
+        let video: &[u8] = b"video";
+
+        let reply = |method, url, video| {
+            route(&method, url, path, None, video).map(|reply| (reply.status, reply.bytes))
+        };
+
+        assert_eq!(
+            reply(tiny_http::Method::Get, path, Some(video)),
+            Ok((200, video))
+        );
+        assert_eq!(
+            reply(tiny_http::Method::Get, "/live/abc.mp4?t=1", Some(video)),
+            Ok((200, video))
+        );
+        assert_eq!(
+            reply(tiny_http::Method::Head, path, Some(video)),
+            Ok((200, video))
+        );
+        assert_eq!(reply(tiny_http::Method::Get, path, None), Err(503));
+        assert_eq!(
+            reply(tiny_http::Method::Get, "/live/other.mp4", Some(video)),
+            Err(404)
+        );
+        assert_eq!(reply(tiny_http::Method::Post, path, Some(video)), Err(404));
+    }
+
+    #[test]
+    fn serves_requested_ranges() {
+        let path = "/live/abc.mp4";
+        let video: &[u8] = b"0123456789";
+
+        let reply = |range| {
+            route(
+                &tiny_http::Method::Get,
+                path,
+                path,
+                Some(range),
+                Some(video),
+            )
+            .map(|reply| (reply.status, reply.bytes, reply.content_range))
+        };
+
+        assert_eq!(
+            reply("bytes=2-4"),
+            Ok((206, &b"234"[..], Some("bytes 2-4/10".to_string())))
+        );
+        assert_eq!(
+            reply("bytes=7-"),
+            Ok((206, &b"789"[..], Some("bytes 7-9/10".to_string())))
+        );
+        assert_eq!(
+            reply("bytes=-2"),
+            Ok((206, &b"89"[..], Some("bytes 8-9/10".to_string())))
+        );
+        assert_eq!(
+            reply("bytes=5-100"),
+            Ok((206, &b"56789"[..], Some("bytes 5-9/10".to_string())))
+        );
+        assert_eq!(reply("bytes=10-"), Err(416));
+
+        // Ignored, so the whole video is served
+        assert_eq!(reply("bytes=0-1,4-5"), Ok((200, video, None)));
+        assert_eq!(reply("items=0-1"), Ok((200, video, None)));
+    }
+}
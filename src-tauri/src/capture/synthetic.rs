@@ -0,0 +1,29 @@
+use super::backend::{CaptureBackend, MonitorInfo, WindowInfo};
+
+/// In-memory backend for tests. Each monitor shows a prepared image.
+pub struct SyntheticBackend {
+    pub monitors: Vec<(MonitorInfo, image::RgbaImage)>,
+    pub windows: Vec<WindowInfo>,
+}
+
+impl CaptureBackend for SyntheticBackend {
+    fn monitors(&self) -> Result<Vec<MonitorInfo>, String> {
+        Ok(self
+            .monitors
+            .iter()
+            .map(|(monitor, _)| monitor.clone())
+            .collect())
+    }
+
+    fn capture_monitor(&self, monitor: &MonitorInfo) -> Result<image::RgbaImage, String> {
+        self.monitors
+            .iter()
+            .find(|(candidate, _)| candidate.index == monitor.index)
+            .map(|(_, image)| image.clone())
+            .ok_or(format!("Monitor {} is gone", monitor.index))
+    }
+
+    fn windows(&self) -> Result<Vec<WindowInfo>, String> {
+        Ok(self.windows.clone())
+    }
+}
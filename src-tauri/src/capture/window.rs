@@ -0,0 +1,284 @@
+use tauri::Manager as _;
+use tauri_specta::Event as _;
+
+use super::backend::{CaptureBackend, WindowInfo};
+use crate::prelude::*;
+
+// NOTE: Matched without extension so it also works for the Proton build on Linux.
+const VRCHAT_PROCESS_NAME: &str = "VRChat";
+
+#[derive(Debug, Clone, serde::Serialize, specta::Type)]
+pub struct CaptureWindow {
+    /// Native handle as a string, because TypeScript numbers cannot hold 64-bit handles.
+    id: String,
+    title: String,
+    process_name: String,
+    width: u32,
+    height: u32,
+}
+
+impl From<&WindowInfo> for CaptureWindow {
+    fn from(window: &WindowInfo) -> Self {
+        Self {
+            id: window.id.to_string(),
+            title: window.title.clone(),
+            process_name: window.process_name.clone(),
+            width: window.bounds.width,
+            height: window.bounds.height,
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn list_capture_windows() -> Result<Vec<CaptureWindow>, String> {
+    let backend = super::backend::default_backend()?;
+
+    Ok(backend
+        .windows()?
+        .iter()
+        .filter(|window| !window.title.is_empty())
+        .map(CaptureWindow::from)
+        .collect())
+}
+
+/// Captures one window without the crop overlay and asks to send it.
+#[tauri::command]
+#[specta::specta]
+pub async fn capture_window(
+    app_handle: tauri::AppHandle,
+    window_id: String,
+    exclude_title_bar: bool,
+    next_action: super::FinishCaptureNextAction,
+) -> Result<(), String> {
+    let window_id: u64 = window_id
+        .parse()
+        .map_err(|e| format!("Invalid window id {}: {}", window_id, e))?;
+
+    // NOTE: Capturing waits for the main window to disappear, which must not block the event loop.
+    tauri::async_runtime::spawn_blocking(move || {
+        capture_window_and_request_send(&app_handle, exclude_title_bar, &next_action, |windows| {
+            find_window(windows, window_id).ok_or(format!("Window {} is gone", window_id))
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to capture window: {e}"))?
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn capture_vrchat_window(
+    app_handle: tauri::AppHandle,
+    exclude_title_bar: bool,
+    next_action: super::FinishCaptureNextAction,
+) -> Result<(), String> {
+    // NOTE: Capturing waits for the main window to disappear, which must not block the event loop.
+    tauri::async_runtime::spawn_blocking(move || {
+        capture_window_and_request_send(&app_handle, exclude_title_bar, &next_action, |windows| {
+            find_vrchat_window(windows).ok_or("VRChat is not running".to_string())
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to capture VRChat window: {e}"))?
+}
+
+fn capture_window_and_request_send(
+    app_handle: &tauri::AppHandle,
+    exclude_title_bar: bool,
+    next_action: &super::FinishCaptureNextAction,
+    select_window: impl FnOnce(&[WindowInfo]) -> Result<&WindowInfo, String>,
+) -> Result<(), String> {
+    let backend = super::backend::default_backend()?;
+
+    let windows = backend.windows()?;
+    let window = select_window(&windows)?;
+
+    info!("Capturing window {:?}", window);
+
+    // NOTE: The window is cropped from the screen on some backends, so our window must not cover it.
+    let full = with_main_window_hidden(app_handle, || backend.capture_window(window))?;
+
+    let auto_saver = super::auto_save::AutoSaver::load(app_handle, "window", next_action);
+
+    let (full, cropped) = match without_title_bar(&full, window, exclude_title_bar) {
+        Some(cropped) => (
+            Some(image::DynamicImage::ImageRgba8(full)),
+            image::DynamicImage::ImageRgba8(cropped),
+        ),
+        None => (None, image::DynamicImage::ImageRgba8(full)),
+    };
+
+    let event = super::save_cropped(full.as_ref(), &cropped, next_action, auto_saver.as_ref())?;
+
+    event
+        .emit(app_handle)
+        .map_err(|e| format!("Failed to emit send_request event: {e}"))
+}
+
+pub(super) fn with_main_window_hidden<T>(
+    app_handle: &tauri::AppHandle,
+    f: impl FnOnce() -> T,
+) -> T {
+    let webview = app_handle
+        .get_webview_window("main")
+        .filter(|window| window.is_visible().unwrap_or(false));
+
+    if let Some(window) = &webview {
+        if let Err(error) = window.hide() {
+            info!("Failed to hide main window: {:?}", error);
+        }
+
+        // wait for the window to disappear from the screen
+        std::thread::sleep(std::time::Duration::from_millis(500));
+    }
+
+    let result = f();
+
+    if let Some(window) = &webview {
+        if let Err(error) = window.show() {
+            info!("Failed to show main window: {:?}", error);
+        }
+    }
+
+    result
+}
+
+pub(super) fn find_window(windows: &[WindowInfo], id: u64) -> Option<&WindowInfo> {
+    windows.iter().find(|window| window.id == id)
+}
+
+/// Returns the largest VRChat window, since the launcher or crash dialogs may share the process name.
+fn find_vrchat_window(windows: &[WindowInfo]) -> Option<&WindowInfo> {
+    windows
+        .iter()
+        .filter(|window| {
+            let process_name = window
+                .process_name
+                .strip_suffix(".exe")
+                .or_else(|| window.process_name.strip_suffix(".EXE"))
+                .unwrap_or(&window.process_name);
+
+            process_name.eq_ignore_ascii_case(VRCHAT_PROCESS_NAME)
+        })
+        .max_by_key(|window| window.bounds.width as u64 * window.bounds.height as u64)
+}
+
+pub(super) fn capture_window_image(
+    backend: &dyn CaptureBackend,
+    window: &WindowInfo,
+    exclude_title_bar: bool,
+) -> Result<image::RgbaImage, String> {
+    let image = backend.capture_window(window)?;
+
+    Ok(without_title_bar(&image, window, exclude_title_bar).unwrap_or(image))
+}
+
+/// Crops the title bar off a window capture, or returns `None` when there is nothing to crop.
+fn without_title_bar(
+    image: &image::RgbaImage,
+    window: &WindowInfo,
+    exclude_title_bar: bool,
+) -> Option<image::RgbaImage> {
+    if !exclude_title_bar || window.title_bar_height == 0 {
+        return None;
+    }
+
+    // NOTE: Only the visible part is captured, so the image may be shorter than the window.
+    let title_bar_height = window
+        .title_bar_height
+        .min(image.height().saturating_sub(1));
+
+    Some(
+        image::imageops::crop_imm(
+            image,
+            0,
+            title_bar_height,
+            image.width(),
+            image.height() - title_bar_height,
+        )
+        .to_image(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::backend::{Bounds, MonitorInfo};
+    use super::super::synthetic::SyntheticBackend;
+    use super::*;
+
+    fn window(id: u64, process_name: &str, width: u32, height: u32) -> WindowInfo {
+        WindowInfo {
+            id,
+            title: format!("Window {}", id),
+            process_name: process_name.to_string(),
+            bounds: Bounds {
+                x: 10,
+                y: 10,
+                width,
+                height,
+            },
+            title_bar_height: 5,
+        }
+    }
+
+    fn backend(windows: Vec<WindowInfo>) -> SyntheticBackend {
+        SyntheticBackend {
+            monitors: vec![(
+                MonitorInfo {
+                    index: 0,
+                    name: "Monitor".to_string(),
+                    bounds: Bounds {
+                        x: 0,
+                        y: 0,
+                        width: 100,
+                        height: 100,
+                    },
+                },
+                // Each row has its own color so crops can be checked by row
+                image::RgbaImage::from_fn(100, 100, |_, y| image::Rgba([y as u8, 0, 0, 255])),
+            )],
+            windows,
+        }
+    }
+
+    #[test]
+    fn finds_vrchat_window_by_process_name() {
+        let windows = vec![
+            window(1, "explorer.exe", 80, 80),
+            window(2, "VRChat.exe", 20, 10),
+            window(3, "VRChat.exe", 60, 40),
+            window(4, "VRChat", 30, 30),
+        ];
+
+        assert_eq!(find_vrchat_window(&windows).unwrap().id, 3);
+        assert!(find_vrchat_window(&windows[..1]).is_none());
+        assert_eq!(find_window(&windows, 4).unwrap().process_name, "VRChat");
+    }
+
+    #[test]
+    fn captures_window_with_and_without_title_bar() {
+        let backend = backend(vec![
+            window(1, "explorer.exe", 20, 20),
+            window(2, "VRChat.exe", 40, 30),
+        ]);
+
+        let windows = backend.windows().unwrap();
+        let vrchat = find_vrchat_window(&windows).unwrap();
+
+        let with_title_bar = capture_window_image(&backend, vrchat, false).unwrap();
+        assert_eq!(with_title_bar.dimensions(), (40, 30));
+        assert_eq!(with_title_bar.get_pixel(0, 0)[0], 10);
+
+        let without_title_bar = capture_window_image(&backend, vrchat, true).unwrap();
+        assert_eq!(without_title_bar.dimensions(), (40, 25));
+        assert_eq!(without_title_bar.get_pixel(0, 0)[0], 15);
+    }
+
+    #[test]
+    fn lists_windows_for_the_frontend() {
+        let window = CaptureWindow::from(&window(u64::MAX, "VRChat.exe", 40, 30));
+
+        assert_eq!(window.id, u64::MAX.to_string());
+        assert_eq!((window.width, window.height), (40, 30));
+    }
+}
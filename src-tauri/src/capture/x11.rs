@@ -0,0 +1,213 @@
+use x11rb::connection::Connection as _;
+use x11rb::protocol::randr::ConnectionExt as _;
+use x11rb::protocol::xproto::{AtomEnum, ConnectionExt as _, GetPropertyReply, ImageFormat};
+
+use super::backend::{Bounds, CaptureBackend, MonitorInfo, WindowInfo};
+
+/// Captures the X11 root window. Also works for XWayland windows, but not for native Wayland ones.
+pub struct X11Backend {
+    connection: x11rb::rust_connection::RustConnection,
+    root: u32,
+    root_bounds: Bounds,
+}
+
+impl X11Backend {
+    pub fn connect() -> Result<Self, String> {
+        let (connection, screen_num) =
+            x11rb::connect(None).map_err(|e| format!("Failed to connect to X server: {e}"))?;
+
+        let screen = &connection.setup().roots[screen_num];
+        let root = screen.root;
+        let root_bounds = Bounds {
+            x: 0,
+            y: 0,
+            width: screen.width_in_pixels as u32,
+            height: screen.height_in_pixels as u32,
+        };
+
+        Ok(Self {
+            connection,
+            root,
+            root_bounds,
+        })
+    }
+
+    fn atom(&self, name: &str) -> Result<u32, String> {
+        Ok(self
+            .connection
+            .intern_atom(false, name.as_bytes())
+            .map_err(|e| format!("Failed to intern {name}: {e}"))?
+            .reply()
+            .map_err(|e| format!("Failed to intern {name}: {e}"))?
+            .atom)
+    }
+
+    fn property(&self, window: u32, property: u32, type_: u32) -> Option<GetPropertyReply> {
+        let reply = self
+            .connection
+            .get_property(false, window, property, type_, 0, u32::MAX / 4)
+            .ok()?
+            .reply()
+            .ok()?;
+
+        // Format is 0 when the property does not exist
+        (reply.format != 0).then_some(reply)
+    }
+
+    fn window_title(&self, window: u32) -> Option<String> {
+        let utf8_title = self
+            .property(
+                window,
+                self.atom("_NET_WM_NAME").ok()?,
+                self.atom("UTF8_STRING").ok()?,
+            )
+            .map(|title| String::from_utf8_lossy(&title.value).into_owned());
+
+        utf8_title.or_else(|| {
+            self.property(window, AtomEnum::WM_NAME.into(), AtomEnum::STRING.into())
+                .map(|title| title.value.iter().map(|&c| c as char).collect())
+        })
+    }
+
+    fn window_process_name(&self, window: u32) -> Option<String> {
+        let pid = self
+            .property(
+                window,
+                self.atom("_NET_WM_PID").ok()?,
+                AtomEnum::CARDINAL.into(),
+            )?
+            .value32()?
+            .next()?;
+
+        std::fs::read_to_string(format!("/proc/{pid}/comm"))
+            .ok()
+            .map(|name| name.trim_end().to_string())
+    }
+
+    fn window_bounds(&self, window: u32) -> Option<Bounds> {
+        let geometry = self.connection.get_geometry(window).ok()?.reply().ok()?;
+
+        let origin = self
+            .connection
+            .translate_coordinates(window, self.root, 0, 0)
+            .ok()?
+            .reply()
+            .ok()?;
+
+        Some(Bounds {
+            x: origin.dst_x as i32,
+            y: origin.dst_y as i32,
+            width: geometry.width as u32,
+            height: geometry.height as u32,
+        })
+    }
+
+    fn capture_region(&self, bounds: &Bounds) -> Result<image::RgbaImage, String> {
+        // NOTE: GetImage fails with BadMatch for areas outside the root window.
+        let bounds = bounds
+            .intersection(&self.root_bounds)
+            .ok_or("Region is outside the screen".to_string())?;
+
+        let reply = self
+            .connection
+            .get_image(
+                ImageFormat::Z_PIXMAP,
+                self.root,
+                bounds.x as i16,
+                bounds.y as i16,
+                bounds.width as u16,
+                bounds.height as u16,
+                !0,
+            )
+            .map_err(|e| format!("Failed to get image: {e}"))?
+            .reply()
+            .map_err(|e| format!("Failed to get image: {e}"))?;
+
+        let pixel_count = (bounds.width * bounds.height) as usize;
+
+        if reply.data.len() != pixel_count * 4 {
+            return Err(format!(
+                "Unsupported X11 pixel format (depth {})",
+                reply.depth
+            ));
+        }
+
+        // 24/32 bit TrueColor visuals are BGRX in memory
+        let rgba = reply
+            .data
+            .chunks_exact(4)
+            .flat_map(|pixel| [pixel[2], pixel[1], pixel[0], 255])
+            .collect();
+
+        image::RgbaImage::from_raw(bounds.width, bounds.height, rgba)
+            .ok_or("Captured image has an unexpected size".to_string())
+    }
+}
+
+impl CaptureBackend for X11Backend {
+    fn monitors(&self) -> Result<Vec<MonitorInfo>, String> {
+        let reply = self
+            .connection
+            .randr_get_monitors(self.root, true)
+            .map_err(|e| format!("Failed to get monitors: {e}"))?
+            .reply()
+            .map_err(|e| format!("Failed to get monitors: {e}"))?;
+
+        Ok(reply
+            .monitors
+            .iter()
+            .enumerate()
+            .map(|(index, monitor)| MonitorInfo {
+                index,
+                name: self
+                    .connection
+                    .get_atom_name(monitor.name)
+                    .ok()
+                    .and_then(|cookie| cookie.reply().ok())
+                    .map(|reply| String::from_utf8_lossy(&reply.name).into_owned())
+                    .unwrap_or_default(),
+                bounds: Bounds {
+                    x: monitor.x as i32,
+                    y: monitor.y as i32,
+                    width: monitor.width as u32,
+                    height: monitor.height as u32,
+                },
+            })
+            .collect())
+    }
+
+    fn capture_monitor(&self, monitor: &MonitorInfo) -> Result<image::RgbaImage, String> {
+        self.capture_region(&monitor.bounds)
+    }
+
+    fn windows(&self) -> Result<Vec<WindowInfo>, String> {
+        let client_list = self
+            .property(
+                self.root,
+                self.atom("_NET_CLIENT_LIST")?,
+                AtomEnum::WINDOW.into(),
+            )
+            .ok_or("Window manager does not provide _NET_CLIENT_LIST".to_string())?;
+
+        let client_list = client_list
+            .value32()
+            .ok_or("_NET_CLIENT_LIST has an unexpected format".to_string())?;
+
+        Ok(client_list
+            .filter_map(|window| {
+                Some(WindowInfo {
+                    id: window as u64,
+                    title: self.window_title(window).unwrap_or_default(),
+                    process_name: self.window_process_name(window).unwrap_or_default(),
+                    bounds: self.window_bounds(window)?,
+                    // Client windows do not include the decorations drawn by the window manager
+                    title_bar_height: 0,
+                })
+            })
+            .collect())
+    }
+
+    fn capture_window(&self, window: &WindowInfo) -> Result<image::RgbaImage, String> {
+        self.capture_region(&window.bounds)
+    }
+}
@@ -0,0 +1,130 @@
+use tauri_plugin_clipboard_manager::ClipboardExt as _;
+
+use crate::prelude::*;
+
+// NOTE: Lets the clipboard watcher ignore images the app put on the clipboard itself.
+static LAST_WRITTEN_IMAGE_HASH: std::sync::Mutex<Option<u64>> = std::sync::Mutex::new(None);
+
+/// Reads the image on the clipboard as PNG bytes.
+pub fn read_clipboard_image(handle: &tauri::AppHandle) -> Result<Vec<u8>, AppError> {
+    let image = handle
+        .clipboard()
+        .read_image()
+        .map_err(AppError::from_error_with_message(
+            "Clipboard does not contain an image",
+        ))?;
+
+    encode_rgba_as_png(image.rgba(), image.width(), image.height())
+}
+
+/// Puts an image on the clipboard. Use this instead of the plugin so the clipboard watcher does not offer it back.
+///
+/// The encoded image is the raw request body, like `send_image_bytes`, and is called through
+/// `writeClipboardImage` in `src/ipc.ts`.
+#[tauri::command]
+pub async fn write_clipboard_image(
+    handle: tauri::AppHandle,
+    request: tauri::ipc::Request<'_>,
+) -> Result<(), AppError> {
+    let tauri::ipc::InvokeBody::Raw(bytes) = request.body() else {
+        return Err(AppError::Unknown(
+            "Image bytes must be sent as a raw body".to_string(),
+        ));
+    };
+
+    write_image(&handle, bytes)
+}
+
+fn write_image(handle: &tauri::AppHandle, bytes: &[u8]) -> Result<(), AppError> {
+    let image = image::load_from_memory(bytes)
+        .map_err(AppError::from_error_with_message("Unsupported image data"))?
+        .to_rgba8();
+
+    let (width, height) = image.dimensions();
+    let rgba = image.into_raw();
+
+    *LAST_WRITTEN_IMAGE_HASH.lock().unwrap() = Some(image_hash(&rgba, width, height));
+
+    handle
+        .clipboard()
+        .write_image(&tauri::image::Image::new_owned(rgba, width, height))
+        .map_err(AppError::from_error_with_message(
+            "Failed to write image to clipboard",
+        ))
+}
+
+/// Counter that changes whenever the clipboard changes, so it can be checked without reading the image.
+/// `None` when the platform has no such counter.
+#[cfg(windows)]
+pub fn clipboard_sequence_number() -> Option<u32> {
+    // SAFETY: Takes no arguments and only reads a counter. 0 means the clipboard is not accessible.
+    let sequence = unsafe { windows::Win32::System::DataExchange::GetClipboardSequenceNumber() };
+
+    (sequence != 0).then_some(sequence)
+}
+
+#[cfg(not(windows))]
+pub fn clipboard_sequence_number() -> Option<u32> {
+    None
+}
+
+pub fn last_written_image_hash() -> Option<u64> {
+    *LAST_WRITTEN_IMAGE_HASH.lock().unwrap()
+}
+
+pub fn image_hash(rgba: &[u8], width: u32, height: u32) -> u64 {
+    use std::hash::{Hash as _, Hasher as _};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    (width, height, rgba).hash(&mut hasher);
+    hasher.finish()
+}
+
+pub fn encode_rgba_as_png(rgba: &[u8], width: u32, height: u32) -> Result<Vec<u8>, AppError> {
+    let image = image::RgbaImage::from_raw(width, height, rgba.to_vec()).ok_or(
+        AppError::Unknown("Clipboard image has an unexpected size".to_string()),
+    )?;
+
+    let mut bytes = std::io::Cursor::new(Vec::new());
+
+    image
+        .write_to(&mut bytes, image::ImageFormat::Png)
+        .map_err(AppError::from_error_with_message(
+            "Failed to encode clipboard image",
+        ))?;
+
+    Ok(bytes.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_rgba_as_png() {
+        let rgba = [255, 0, 0, 255].repeat(6);
+
+        let png = encode_rgba_as_png(&rgba, 3, 2).unwrap();
+        let decoded = image::load_from_memory(&png).unwrap();
+
+        assert_eq!((decoded.width(), decoded.height()), (3, 2));
+        assert_eq!(decoded.to_rgba8().into_raw(), rgba);
+    }
+
+    #[test]
+    fn hashes_pixels_and_size() {
+        let rgba = [0, 0, 0, 255].repeat(6);
+
+        assert_eq!(image_hash(&rgba, 3, 2), image_hash(&rgba, 3, 2));
+        assert_ne!(image_hash(&rgba, 3, 2), image_hash(&rgba, 2, 3));
+        assert_ne!(
+            image_hash(&rgba, 3, 2),
+            image_hash(&[255, 0, 0, 255].repeat(6), 3, 2)
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_size() {
+        assert!(encode_rgba_as_png(&[0; 4], 2, 2).is_err());
+    }
+}
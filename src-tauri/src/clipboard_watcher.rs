@@ -0,0 +1,238 @@
+use tauri::Manager as _;
+use tauri_plugin_clipboard_manager::ClipboardExt as _;
+use tauri_specta::Event as _;
+
+use crate::prelude::*;
+
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+#[derive(Debug, Clone, PartialEq)]
+struct WatcherSettings {
+    mode: Option<crate::SendRequestEventMode>,
+    auto_send: bool,
+}
+
+impl WatcherSettings {
+    fn load(handle: &tauri::AppHandle) -> Option<Self> {
+        let state = handle.state::<crate::app_data::AppData>();
+
+        if !state.is_healthy() {
+            return None;
+        }
+
+        let config = state.lock_config();
+
+        if !config.get_bool("clipboard_watcher_enabled", false) {
+            return None;
+        }
+
+        let mode = match config.get("clipboard_watcher_action") {
+            Some("UploadImageToVideoServer") => {
+                Some(crate::SendRequestEventMode::UploadImageToVideoServer)
+            }
+            Some("UploadImageToImageServer") => {
+                Some(crate::SendRequestEventMode::UploadImageToImageServer)
+            }
+            Some("UploadImageToVRChatPrint") => {
+                Some(crate::SendRequestEventMode::UploadImageToVRChatPrint)
+            }
+            _ => None,
+        };
+
+        let auto_send = mode.is_some() && config.get_bool("clipboard_watcher_auto_send", false);
+
+        Some(Self { mode, auto_send })
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, specta::Type, tauri_specta::Event)]
+pub struct ClipboardImageDetectedEvent {
+    file: String,
+    auto_send: bool,
+}
+
+pub fn init_clipboard_watcher(app_handle: tauri::AppHandle) {
+    std::thread::spawn(move || clipboard_watcher_thread(app_handle));
+}
+
+fn clipboard_watcher_thread(app_handle: tauri::AppHandle) {
+    let mut watching: Option<(WatcherSettings, NewImageDetector)> = None;
+
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+
+        let settings = WatcherSettings::load(&app_handle);
+
+        // The first poll after (re)starting only records what is already on the clipboard.
+        if watching.as_ref().map(|(current, _)| current) != settings.as_ref() {
+            watching = settings.map(|settings| {
+                info!("Watching clipboard for images");
+                (settings, NewImageDetector::new())
+            });
+        }
+
+        let Some((settings, detector)) = &mut watching else {
+            continue;
+        };
+
+        // NOTE: Decoding and hashing the image every poll is expensive, so skip it while the clipboard is unchanged.
+        if !detector.may_have_changed(crate::clipboard::clipboard_sequence_number()) {
+            continue;
+        }
+
+        // NOTE: Fails when the clipboard holds no image (e.g. text).
+        let image = app_handle.clipboard().read_image().ok();
+
+        let hash = image
+            .as_ref()
+            .map(|image| crate::clipboard::image_hash(image.rgba(), image.width(), image.height()));
+
+        if !detector.poll(hash, crate::clipboard::last_written_image_hash()) {
+            continue;
+        }
+
+        let Some(image) = image else {
+            continue;
+        };
+
+        info!("New clipboard image detected");
+
+        let file =
+            match crate::clipboard::encode_rgba_as_png(image.rgba(), image.width(), image.height())
+                .and_then(|png| crate::jobs::save_image_bytes_to_workspace(&png))
+            {
+                Ok(file) => file.to_string_lossy().into_owned(),
+                Err(e) => {
+                    error!("Failed to save clipboard image: {:?}", e);
+                    continue;
+                }
+            };
+
+        handle_new_image(&app_handle, settings, &file);
+    }
+}
+
+fn handle_new_image(app_handle: &tauri::AppHandle, settings: &WatcherSettings, file: &str) {
+    ClipboardImageDetectedEvent {
+        file: file.to_string(),
+        auto_send: settings.auto_send,
+    }
+    .emit(app_handle)
+    .unwrap_or_else(|e| debug!("Failed to emit clipboard image detected event: {:?}", e));
+
+    if let (true, Some(mode)) = (settings.auto_send, &settings.mode) {
+        if let Err(e) = tauri::async_runtime::block_on(crate::send::send_file_and_report(
+            app_handle,
+            file,
+            mode.clone(),
+        )) {
+            error!("Failed to auto-send clipboard image: {:?}", e);
+        }
+        return;
+    }
+
+    crate::SendRequestEvent {
+        file: file.to_string(),
+        mode: settings.mode.clone(),
+        preselected_mode: None,
+        copy: None,
+        saved_files: Vec::new(),
+    }
+    .emit(app_handle)
+    .unwrap_or_else(|e| debug!("Failed to emit send request event: {:?}", e));
+}
+
+/// Tells whether the clipboard image changed since the last poll, ignoring images written by the app.
+struct NewImageDetector {
+    initialized: bool,
+    last_hash: Option<u64>,
+    last_sequence: Option<u32>,
+}
+
+impl NewImageDetector {
+    fn new() -> Self {
+        Self {
+            initialized: false,
+            last_hash: None,
+            last_sequence: None,
+        }
+    }
+
+    /// Tells whether the clipboard has to be read. Without a sequence number every poll reads it.
+    fn may_have_changed(&mut self, sequence: Option<u32>) -> bool {
+        if sequence.is_some() && self.last_sequence == sequence {
+            return false;
+        }
+
+        self.last_sequence = sequence;
+
+        true
+    }
+
+    fn poll(&mut self, hash: Option<u64>, own_hash: Option<u64>) -> bool {
+        let is_first_poll = !self.initialized;
+        self.initialized = true;
+
+        // NOTE: Copying text in between does not make the same image new again.
+        let Some(hash) = hash else {
+            return false;
+        };
+
+        if self.last_hash == Some(hash) {
+            return false;
+        }
+
+        self.last_hash = Some(hash);
+
+        !is_first_poll && own_hash != Some(hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignores_image_already_on_clipboard() {
+        let mut detector = NewImageDetector::new();
+
+        assert!(!detector.poll(Some(1), None));
+        assert!(!detector.poll(Some(1), None));
+        assert!(detector.poll(Some(2), None));
+    }
+
+    #[test]
+    fn detects_each_image_once() {
+        let mut detector = NewImageDetector::new();
+
+        assert!(!detector.poll(None, None));
+        assert!(detector.poll(Some(1), None));
+        assert!(!detector.poll(Some(1), None));
+        assert!(!detector.poll(None, None));
+        assert!(!detector.poll(Some(1), None));
+        assert!(detector.poll(Some(2), None));
+        assert!(detector.poll(Some(1), None));
+    }
+
+    #[test]
+    fn reads_clipboard_only_after_sequence_changes() {
+        let mut detector = NewImageDetector::new();
+
+        assert!(detector.may_have_changed(Some(1)));
+        assert!(!detector.may_have_changed(Some(1)));
+        assert!(detector.may_have_changed(Some(2)));
+
+        // Platforms without a sequence number
+        assert!(detector.may_have_changed(None));
+        assert!(detector.may_have_changed(None));
+    }
+
+    #[test]
+    fn ignores_images_written_by_the_app() {
+        let mut detector = NewImageDetector::new();
+
+        assert!(!detector.poll(None, None));
+        assert!(!detector.poll(Some(1), Some(1)));
+        assert!(detector.poll(Some(2), Some(1)));
+    }
+}
@@ -5,6 +5,29 @@ use crate::prelude::*;
 
 type ConfigMap = std::collections::HashMap<String, String>;
 
+// NOTE: Must match `identifier` in tauri.conf.json, which decides `app_config_dir`.
+const APP_IDENTIFIER: &str = "com.superneko.send-to-vrc";
+
+// NOTE: Set once from `--profile` at startup, before anything reads the config.
+static PROFILE: std::sync::OnceLock<Option<String>> = std::sync::OnceLock::new();
+
+pub fn set_profile(profile: Option<String>) {
+    if PROFILE.set(profile).is_err() {
+        warn!("Profile is already set");
+    }
+}
+
+pub fn profile() -> Option<&'static str> {
+    PROFILE.get().and_then(|profile| profile.as_deref())
+}
+
+fn config_file_name() -> String {
+    match profile() {
+        Some(profile) => format!("config.{}.ini", profile),
+        None => "config.ini".to_string(),
+    }
+}
+
 #[derive(Debug)]
 pub struct Config {
     config_map: ConfigMap,
@@ -31,6 +54,13 @@ impl Config {
         self.config_map.get(key).map(|s| s.as_str())
     }
 
+    pub fn get_bool(&self, key: &str, default: bool) -> bool {
+        match self.get(key) {
+            Some(value) => value == "true",
+            None => default,
+        }
+    }
+
     pub fn set(&mut self, key: String, value: String) -> Result<(), AppError> {
         self.config_map.insert(key, value);
         save_config_file(&self.path, &self.config_map)
@@ -127,11 +157,20 @@ pub fn config_file_path(handle: tauri::AppHandle) -> Result<std::path::PathBuf,
         .map_err(AppError::from_error_with_message(
             "Failed to get app config directory",
         ))?
-        .join("config.ini");
+        .join(config_file_name());
 
     Ok(config_path)
 }
 
+/// Same path as `config_file_path`, for the headless mode where Tauri is not running.
+pub fn config_file_path_without_app_handle() -> Result<std::path::PathBuf, AppError> {
+    let config_dir = dirs::config_dir().ok_or(AppError::ConfigDirectoryExistance(
+        "Failed to get config directory".to_string(),
+    ))?;
+
+    Ok(config_dir.join(APP_IDENTIFIER).join(config_file_name()))
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn reset_config(handle: tauri::AppHandle) -> Result<(), AppError> {
@@ -0,0 +1,368 @@
+use std::io::Read as _;
+
+use tauri::Manager as _;
+
+use crate::prelude::*;
+
+const DEFAULT_PORT: u16 = 17380;
+const RECEIVE_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(500);
+const MAX_JSON_BYTES: u64 = 64 * 1024;
+const MAX_UPLOAD_BYTES: u64 = 50 * 1024 * 1024;
+
+static CONTROL_API: std::sync::Mutex<Option<ControlApiThread>> = std::sync::Mutex::new(None);
+
+struct ControlApiThread {
+    stop: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    thread: std::thread::JoinHandle<()>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Route {
+    Send,
+    Upload,
+    Capture,
+    History,
+    Job(u32),
+}
+
+#[derive(serde::Deserialize)]
+struct SendBody {
+    file: String,
+    mode: String,
+}
+
+struct ApiError {
+    status: u16,
+    error: AppError,
+}
+
+impl ApiError {
+    fn new(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            error: AppError::Unknown(message.to_string()),
+        }
+    }
+}
+
+impl From<AppError> for ApiError {
+    fn from(error: AppError) -> Self {
+        Self { status: 400, error }
+    }
+}
+
+fn route(method: &tiny_http::Method, path: &str) -> Option<Route> {
+    let segments: Vec<&str> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+
+    match (method, segments.as_slice()) {
+        (tiny_http::Method::Post, ["send"]) => Some(Route::Send),
+        (tiny_http::Method::Post, ["upload"]) => Some(Route::Upload),
+        (tiny_http::Method::Post, ["capture"]) => Some(Route::Capture),
+        (tiny_http::Method::Get, ["history"]) => Some(Route::History),
+        (tiny_http::Method::Get, ["jobs", id]) => id.parse().ok().map(Route::Job),
+        _ => None,
+    }
+}
+
+fn query_param(query: &str, name: &str) -> Option<String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .and_then(|(_, value)| urlencoding::decode(value).ok())
+        .map(|value| value.into_owned())
+}
+
+/// Accepts `Authorization: Bearer <token>`, or `?token=` for tools that cannot set headers.
+fn is_authorized(header: Option<&str>, query_token: Option<&str>, token: &str) -> bool {
+    let provided = header
+        .and_then(|header| header.strip_prefix("Bearer "))
+        .or(query_token);
+
+    // NOTE: Compared in constant time so the token cannot be guessed byte by byte.
+    provided.is_some_and(|provided| {
+        provided.len() == token.len()
+            && provided
+                .bytes()
+                .zip(token.bytes())
+                .fold(0, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    })
+}
+
+pub(crate) fn generate_token() -> Result<String, AppError> {
+    let mut bytes = [0u8; 16];
+
+    getrandom::fill(&mut bytes).map_err(AppError::from_error_with_message(
+        "Failed to read the OS random number generator",
+    ))?;
+
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+fn read_body(request: &mut tiny_http::Request, limit: u64) -> Result<Vec<u8>, ApiError> {
+    let mut body = Vec::new();
+
+    request
+        .as_reader()
+        .take(limit + 1)
+        .read_to_end(&mut body)
+        .map_err(|e| ApiError::new(400, &format!("Failed to read request body: {}", e)))?;
+
+    if body.len() as u64 > limit {
+        return Err(ApiError::new(413, "Request body is too large"));
+    }
+
+    Ok(body)
+}
+
+fn handle_request(
+    app_handle: &tauri::AppHandle,
+    token: &str,
+    request: &mut tiny_http::Request,
+) -> Result<(u16, serde_json::Value), ApiError> {
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+
+    let authorization = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Authorization"))
+        .map(|header| header.value.as_str().to_string());
+
+    if !is_authorized(
+        authorization.as_deref(),
+        query_param(query, "token").as_deref(),
+        token,
+    ) {
+        return Err(ApiError::new(401, "Invalid or missing token"));
+    }
+
+    let Some(route) = route(request.method(), path) else {
+        return Err(ApiError::new(404, "Not found"));
+    };
+
+    match route {
+        Route::Send => {
+            let body: SendBody = serde_json::from_slice(&read_body(request, MAX_JSON_BYTES)?)
+                .map_err(|e| ApiError::new(400, &format!("Invalid request body: {}", e)))?;
+
+            let mode = crate::launch_options::parse_send_mode(&body.mode)?;
+
+            if !crate::remote_image::is_remote_url(&body.file)
+                && !std::path::Path::new(&body.file).is_file()
+            {
+                return Err(ApiError::new(400, "File does not exist"));
+            }
+
+            let job = crate::send::spawn_send_job(app_handle, body.file, mode);
+
+            Ok((202, serde_json::json!({ "job": job })))
+        }
+        Route::Upload => {
+            let Some(mode) = query_param(query, "mode") else {
+                return Err(ApiError::new(400, "mode query parameter is required"));
+            };
+
+            let mode = crate::launch_options::parse_send_mode(&mode)?;
+
+            let path =
+                crate::jobs::save_image_bytes_to_workspace(&read_body(request, MAX_UPLOAD_BYTES)?)?;
+
+            let job =
+                crate::send::spawn_send_job(app_handle, path.to_string_lossy().into_owned(), mode);
+
+            Ok((202, serde_json::json!({ "job": job })))
+        }
+        Route::Capture => {
+            crate::capture::start_capture().map_err(|e| ApiError {
+                status: 500,
+                error: AppError::Unknown(e),
+            })?;
+
+            Ok((202, serde_json::json!({})))
+        }
+        Route::History => Ok((200, serde_json::json!(crate::jobs::history()))),
+        Route::Job(id) => crate::jobs::job(id)
+            .map(|job| (200, serde_json::json!(job)))
+            .ok_or(ApiError::new(404, "Job not found")),
+    }
+}
+
+pub fn init_control_api(app_handle: tauri::AppHandle) {
+    // NOTE: Hold the lock until the new thread is stored so concurrent restarts cannot start two.
+    let mut current = CONTROL_API.lock().unwrap();
+
+    // NOTE: Wait for the previous server to release the port before binding it again.
+    if let Some(previous) = current.take() {
+        previous
+            .stop
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+        previous
+            .thread
+            .join()
+            .unwrap_or_else(|_| error!("Control API thread panicked"));
+    }
+
+    let (port, token) = {
+        let state = app_handle.state::<crate::app_data::AppData>();
+
+        if !state.is_healthy() {
+            return;
+        }
+
+        let mut config = state.lock_config();
+
+        if !config.get_bool("control_api_enabled", false) {
+            info!("Control API is disabled");
+            return;
+        }
+
+        let port = config
+            .get("control_api_port")
+            .and_then(|port| port.parse().ok())
+            .unwrap_or(DEFAULT_PORT);
+
+        let token = match config
+            .get("control_api_token")
+            .filter(|token| !token.is_empty())
+        {
+            Some(token) => token.to_string(),
+            None => {
+                let token = match generate_token() {
+                    Ok(token) => token,
+                    Err(e) => {
+                        error!("Failed to generate control API token: {:?}", e);
+                        return;
+                    }
+                };
+
+                if let Err(e) = config.set("control_api_token".to_string(), token.clone()) {
+                    error!("Failed to save control API token: {:?}", e);
+                    return;
+                }
+
+                token
+            }
+        };
+
+        (port, token)
+    };
+
+    let server = match tiny_http::Server::http(("127.0.0.1", port)) {
+        Ok(server) => server,
+        Err(e) => {
+            error!("Failed to start control API on port {}: {}", port, e);
+            return;
+        }
+    };
+
+    info!("Control API is listening on port {}", port);
+
+    let stop = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+    let thread = {
+        let stop = stop.clone();
+        std::thread::spawn(move || serve(&app_handle, server, &stop, &token))
+    };
+
+    *current = Some(ControlApiThread { stop, thread });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn restart_control_api(handle: tauri::AppHandle) {
+    // NOTE: Stopping waits for the previous thread to exit, which must not block the event loop.
+    tauri::async_runtime::spawn_blocking(move || init_control_api(handle))
+        .await
+        .unwrap_or_else(|e| error!("Failed to restart control API: {:?}", e));
+}
+
+fn serve(
+    app_handle: &tauri::AppHandle,
+    server: tiny_http::Server,
+    stop: &std::sync::atomic::AtomicBool,
+    token: &str,
+) {
+    while !stop.load(std::sync::atomic::Ordering::SeqCst) {
+        let mut request = match server.recv_timeout(RECEIVE_TIMEOUT) {
+            Ok(Some(request)) => request,
+            Ok(None) => continue,
+            Err(e) => {
+                error!("Control API failed: {:?}", e);
+                return;
+            }
+        };
+
+        let (status, body) = match handle_request(app_handle, token, &mut request) {
+            Ok(response) => response,
+            Err(e) => {
+                info!("Control API request failed: {} {:?}", e.status, e.error);
+                (e.status, serde_json::json!({ "error": e.error }))
+            }
+        };
+
+        let response = tiny_http::Response::from_string(body.to_string())
+            .with_status_code(status)
+            .with_header(
+                tiny_http::Header::from_bytes("Content-Type", "application/json")
+                    .expect("Content-Type header is valid"),
+            );
+
+        request
+            .respond(response)
+            .unwrap_or_else(|e| debug!("Failed to respond to control API request: {:?}", e));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_requests() {
+        assert_eq!(route(&tiny_http::Method::Post, "/send"), Some(Route::Send));
+        assert_eq!(
+            route(&tiny_http::Method::Post, "/upload/"),
+            Some(Route::Upload)
+        );
+        assert_eq!(
+            route(&tiny_http::Method::Get, "/jobs/12"),
+            Some(Route::Job(12))
+        );
+        assert_eq!(route(&tiny_http::Method::Get, "/jobs/abc"), None);
+        assert_eq!(route(&tiny_http::Method::Get, "/send"), None);
+        assert_eq!(route(&tiny_http::Method::Get, "/"), None);
+    }
+
+    #[test]
+    fn parses_query_params() {
+        assert_eq!(
+            query_param("mode=print&token=a%20b", "token"),
+            Some("a b".to_string())
+        );
+        assert_eq!(query_param("mode=print", "token"), None);
+        assert_eq!(query_param("", "mode"), None);
+    }
+
+    #[test]
+    fn checks_token() {
+        assert!(is_authorized(Some("Bearer secret"), None, "secret"));
+        assert!(is_authorized(None, Some("secret"), "secret"));
+        assert!(!is_authorized(Some("secret"), None, "secret"));
+        assert!(!is_authorized(Some("Bearer secreT"), None, "secret"));
+        assert!(!is_authorized(None, Some("secrets"), "secret"));
+        assert!(!is_authorized(None, None, "secret"));
+    }
+
+    #[test]
+    fn generates_unique_tokens() {
+        let token = generate_token().unwrap();
+
+        assert_eq!(token.len(), 32);
+        assert_ne!(token, generate_token().unwrap());
+    }
+}
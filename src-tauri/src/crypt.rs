@@ -1,8 +1,9 @@
-use windows::Win32::Security::Cryptography::{CryptProtectData, CRYPT_INTEGER_BLOB};
-
 use crate::error::AppError;
 
+#[cfg(windows)]
 pub fn crypt(slice: &[u8]) -> Result<Vec<u8>, AppError> {
+    use windows::Win32::Security::Cryptography::{CryptProtectData, CRYPT_INTEGER_BLOB};
+
     unsafe {
         let pdatain = CRYPT_INTEGER_BLOB {
             cbData: slice.len() as u32,
@@ -40,6 +41,7 @@ pub fn crypt(slice: &[u8]) -> Result<Vec<u8>, AppError> {
     }
 }
 
+#[cfg(windows)]
 pub fn decrypt(slice: &[u8]) -> Result<Vec<u8>, AppError> {
     use windows::Win32::Security::Cryptography::{CryptUnprotectData, CRYPT_INTEGER_BLOB};
 
@@ -78,6 +80,17 @@ pub fn decrypt(slice: &[u8]) -> Result<Vec<u8>, AppError> {
     }
 }
 
+// NOTE: DPAPI is Windows only. Elsewhere the value is stored in the config file as is.
+#[cfg(not(windows))]
+pub fn crypt(slice: &[u8]) -> Result<Vec<u8>, AppError> {
+    Ok(slice.to_vec())
+}
+
+#[cfg(not(windows))]
+pub fn decrypt(slice: &[u8]) -> Result<Vec<u8>, AppError> {
+    Ok(slice.to_vec())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,253 @@
+use tauri_plugin_deep_link::DeepLinkExt as _;
+
+use crate::launch_options::{LaunchOptions, LaunchOptionsMode, SendOptions};
+use crate::prelude::*;
+
+// NOTE: Must match `plugins.deep-link.desktop.schemes` in tauri.conf.json.
+pub const SCHEME: &str = "send-to-vrc";
+
+// Same as the file associations in windows/hooks.nsh
+const IMAGE_EXTENSIONS: [&str; 10] = [
+    "avif", "bmp", "exr", "gif", "jpeg", "jpg", "png", "pnm", "tiff", "webp",
+];
+
+pub fn is_deep_link(arg: &str) -> bool {
+    arg.get(..SCHEME.len())
+        .is_some_and(|scheme| scheme.eq_ignore_ascii_case(SCHEME))
+        && arg[SCHEME.len()..].starts_with(':')
+}
+
+/// Parses `send-to-vrc://send?file=...` and `send-to-vrc://capture`.
+///
+/// Deep links can be opened by any web page, so only existing local image files are accepted.
+/// `mode` (`video`, `image` or `print`) is only pre-selected, so a page never sends without the user.
+pub fn parse_deep_link(link: &str) -> Result<LaunchOptionsMode, AppError> {
+    let url =
+        reqwest::Url::parse(link).map_err(|e| invalid_link(&format!("Malformed URL ({})", e)))?;
+
+    if url.scheme() != SCHEME {
+        return Err(invalid_link("Unknown scheme"));
+    }
+
+    if !url.username().is_empty()
+        || url.password().is_some()
+        || url.port().is_some()
+        || url.fragment().is_some()
+        || !matches!(url.path(), "" | "/")
+    {
+        return Err(invalid_link("Unexpected URL components"));
+    }
+
+    match url.host_str() {
+        Some("capture") => {
+            if url.query_pairs().next().is_some() {
+                return Err(invalid_link("capture does not take parameters"));
+            }
+            Ok(LaunchOptionsMode::Capture)
+        }
+        Some("send") => {
+            let mut files = Vec::new();
+            let mut options = SendOptions::default();
+
+            for (key, value) in url.query_pairs() {
+                match key.as_ref() {
+                    "file" => files.push(validate_file(&value)?),
+                    "mode" => {
+                        let mode = crate::launch_options::parse_send_mode(&value)
+                            .map_err(|_| invalid_link(&format!("Unknown mode: {}", value)))?;
+
+                        options.preselected_mode = Some(mode);
+                    }
+                    _ => return Err(invalid_link(&format!("Unexpected parameter: {}", key))),
+                }
+            }
+
+            if files.is_empty() {
+                return Err(invalid_link("send requires a file parameter"));
+            }
+
+            Ok(LaunchOptionsMode::Send { files, options })
+        }
+        _ => Err(invalid_link("Unknown action")),
+    }
+}
+
+/// Registers the scheme and handles links the OS hands to the running instance.
+///
+/// Windows and Linux start a new instance for each link instead, which forwards it through `single_instance`.
+pub fn init_deep_link(app_handle: tauri::AppHandle) {
+    // NOTE: The installer registers the scheme on Windows. Development builds are not installed.
+    #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+    {
+        app_handle
+            .deep_link()
+            .register_all()
+            .unwrap_or_else(|e| warn!("Failed to register deep link scheme: {:?}", e));
+    }
+
+    let handle = app_handle.clone();
+
+    app_handle.deep_link().on_open_url(move |event| {
+        for url in event.urls() {
+            match parse_deep_link(url.as_str()) {
+                Ok(mode) => {
+                    crate::single_instance::handle_launch_options(&handle, LaunchOptions { mode })
+                }
+                Err(e) => warn!("Ignored deep link {}: {:?}", url, e),
+            }
+        }
+    });
+}
+
+fn invalid_link(reason: &str) -> AppError {
+    AppError::Unknown(format!("Invalid deep link: {}", reason))
+}
+
+fn validate_file(file: &str) -> Result<String, AppError> {
+    let path = std::path::Path::new(file);
+
+    if !path.is_absolute() {
+        return Err(invalid_link("file must be an absolute path"));
+    }
+
+    // NOTE: Opening a network path can leak credentials (NTLM) to the remote host.
+    if let Some(std::path::Component::Prefix(prefix)) = path.components().next() {
+        if !matches!(
+            prefix.kind(),
+            std::path::Prefix::Disk(_) | std::path::Prefix::VerbatimDisk(_)
+        ) {
+            return Err(invalid_link("file must be on a local drive"));
+        }
+    }
+
+    let is_image = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| IMAGE_EXTENSIONS.contains(&extension.to_lowercase().as_str()));
+
+    if !is_image {
+        return Err(invalid_link("file must be an image"));
+    }
+
+    if !path.is_file() {
+        return Err(invalid_link("file does not exist"));
+    }
+
+    Ok(file.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn existing_image() -> String {
+        let path = crate::file::temp_file_path("deep_link_test_image.png");
+        std::fs::write(&path, b"").unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    fn link(action: &str, query: &[(&str, &str)]) -> String {
+        let query: Vec<String> = query
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, urlencoding::encode(value)))
+            .collect();
+
+        format!("send-to-vrc://{}?{}", action, query.join("&"))
+    }
+
+    #[test]
+    fn detects_deep_links() {
+        assert!(is_deep_link("send-to-vrc://capture"));
+        assert!(is_deep_link("SEND-TO-VRC://capture"));
+        assert!(!is_deep_link("send-to-vrc"));
+        assert!(!is_deep_link("send-to-vrc.png"));
+        assert!(!is_deep_link("C:\\send-to-vrc\\a.png"));
+        assert!(!is_deep_link("スクリーンショット.png"));
+    }
+
+    #[test]
+    fn parses_capture() {
+        assert_eq!(
+            parse_deep_link("send-to-vrc://capture").unwrap(),
+            LaunchOptionsMode::Capture
+        );
+        assert_eq!(
+            parse_deep_link("send-to-vrc://capture/").unwrap(),
+            LaunchOptionsMode::Capture
+        );
+        assert!(parse_deep_link("send-to-vrc://capture?mode=print").is_err());
+    }
+
+    #[test]
+    fn parses_send() {
+        let file = existing_image();
+
+        assert_eq!(
+            parse_deep_link(&link("send", &[("file", &file)])).unwrap(),
+            LaunchOptionsMode::Send {
+                files: vec![file.clone()],
+                options: SendOptions::default(),
+            }
+        );
+
+        assert_eq!(
+            parse_deep_link(&link("send", &[("file", &file), ("file", &file)])).unwrap(),
+            LaunchOptionsMode::Send {
+                files: vec![file.clone(), file],
+                options: SendOptions::default(),
+            }
+        );
+    }
+
+    #[test]
+    fn preselects_mode() {
+        let file = existing_image();
+
+        let LaunchOptionsMode::Send { options, .. } =
+            parse_deep_link(&link("send", &[("file", &file), ("mode", "print")])).unwrap()
+        else {
+            panic!("Expected send mode");
+        };
+
+        assert_eq!(options.mode, None);
+        assert_eq!(
+            options.preselected_mode,
+            Some(crate::SendRequestEventMode::UploadImageToVRChatPrint)
+        );
+
+        for mode in ["delete", "", "UploadImageToVRChatPrint"] {
+            assert!(
+                parse_deep_link(&link("send", &[("file", &file), ("mode", mode)])).is_err(),
+                "mode={} must be rejected",
+                mode
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_unsafe_or_unknown_input() {
+        let file = existing_image();
+
+        for link in [
+            "https://send?file=a.png".to_string(),
+            "send-to-vrc://delete".to_string(),
+            "send-to-vrc://send".to_string(),
+            "send-to-vrc://user@send?file=a.png".to_string(),
+            "send-to-vrc://send/extra?file=a.png".to_string(),
+            link("send", &[("file", "relative.png")]),
+            link("send", &[("file", &format!("{}.missing.png", file))]),
+            link("send", &[("file", "/etc/passwd")]),
+            link("send", &[("file", "https://example.com/a.png")]),
+            link("send", &[("file", &file), ("no_gui", "true")]),
+        ] {
+            assert!(parse_deep_link(&link).is_err(), "{} must be rejected", link);
+        }
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn rejects_network_paths() {
+        assert!(validate_file(r"\\attacker\share\a.png").is_err());
+        assert!(validate_file(r"\\?\UNC\attacker\share\a.png").is_err());
+    }
+}
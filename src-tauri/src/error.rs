@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use specta::Type;
 use thiserror::Error;
 
-#[derive(Serialize, Deserialize, Debug, Type, Error)]
+#[derive(Serialize, Deserialize, Debug, Clone, Type, Error)]
 #[serde(tag = "type", content = "message")]
 pub enum AppError {
     #[error("Configuration file is malformed or contains invalid data.")]
@@ -17,6 +17,8 @@ pub enum AppError {
     VrchatAuthRequired(String),
     #[error("VRChat Print needs valid VRChat Plus subscription.")]
     VrchatPlusRequired(String),
+    #[error("VRChat API rate limit exceeded. Please wait and try again.")]
+    VrchatRateLimited(String),
     #[error("Unknown error occurred.")]
     Unknown(String),
 }
@@ -0,0 +1,307 @@
+use crate::launch_options::{LaunchOptions, LaunchOptionsMode};
+use crate::prelude::*;
+
+const EXIT_SUCCESS: i32 = 0;
+const EXIT_USAGE: i32 = 2;
+
+#[derive(serde::Serialize)]
+struct HeadlessResult<'a> {
+    file: &'a str,
+    mode: &'a crate::SendRequestEventMode,
+    outcome: crate::send::SendOutcome,
+}
+
+/// Handles the launches that must not open a window. Returns the exit code if the process should exit.
+pub fn run_command_line(args: &[String]) -> Option<i32> {
+    let launch_options = crate::launch_options::parse_launch_options(args.to_vec())
+        .and_then(crate::launch_options::with_stdin_image);
+
+    let is_headless = args.iter().any(|arg| arg == "--no-gui");
+
+    let exit_code = match launch_options {
+        Ok(LaunchOptions {
+            mode: LaunchOptionsMode::Help,
+        }) => {
+            attach_parent_console();
+            println!("{}", crate::launch_options::USAGE);
+            EXIT_SUCCESS
+        }
+        Ok(LaunchOptions {
+            mode: LaunchOptionsMode::Version,
+        }) => {
+            attach_parent_console();
+            println!("send-to-vrc {}", env!("CARGO_PKG_VERSION"));
+            EXIT_SUCCESS
+        }
+        Ok(LaunchOptions {
+            mode: LaunchOptionsMode::Send { files, options },
+        }) if options.no_gui => {
+            attach_parent_console();
+
+            crate::config::set_profile(options.profile.clone());
+
+            match options.mode {
+                Some(mode) => run_headless_send(&files, &mode, options.json),
+                None => usage_error("--no-gui requires --mode"),
+            }
+        }
+        Err(e) if is_headless => {
+            attach_parent_console();
+            usage_error(&error_message(&e))
+        }
+        // The frontend shows the parse error
+        _ => return None,
+    };
+
+    Some(exit_code)
+}
+
+fn usage_error(message: &str) -> i32 {
+    eprintln!("error: {}", message);
+    eprintln!("{}", crate::launch_options::USAGE);
+    EXIT_USAGE
+}
+
+fn run_headless_send(files: &[String], mode: &crate::SendRequestEventMode, json: bool) -> i32 {
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("error: Failed to start async runtime: {}", e);
+            return exit_code(&AppError::Unknown(e.to_string()));
+        }
+    };
+
+    let mut first_error = None;
+
+    for file in files {
+        let result = runtime.block_on(send_headless(file, mode));
+
+        if json {
+            println!("{}", json_result(file, mode, &result));
+        } else {
+            match &result {
+                Ok(Some(url)) => println!("{}", url),
+                Ok(None) => {}
+                Err(e) => eprintln!("error: {}: {}", file, error_message(e)),
+            }
+        }
+
+        if let Err(e) = result {
+            first_error.get_or_insert(e);
+        }
+    }
+
+    // NOTE: Only the image read from stdin is in a workspace, other files are left alone.
+    for file in files {
+        crate::jobs::remove_workspace_of(file);
+    }
+
+    match &first_error {
+        None => EXIT_SUCCESS,
+        Some(e) => exit_code(e),
+    }
+}
+
+async fn send_headless(
+    file: &str,
+    mode: &crate::SendRequestEventMode,
+) -> Result<Option<String>, AppError> {
+    let config = crate::config::Config::new(crate::config::config_file_path_without_app_handle()?)?;
+
+    let context = crate::send::SendContext {
+        ffmpeg_path: bundled_ffmpeg_path()?,
+        config: std::sync::Arc::new(std::sync::Mutex::new(config)),
+        progress_callback: Some(Box::new(|progress| {
+            eprintln!("{}", progress_message(&progress))
+        })),
+        queue_callback: Some(Box::new(|status| eprintln!("{}", queue_message(&status)))),
+    };
+
+    crate::send::send_file(&context, file, mode).await
+}
+
+fn progress_message(progress: &crate::progress::Progress) -> &'static str {
+    match progress {
+        crate::progress::Progress::Starting => "Starting...",
+        crate::progress::Progress::Compressing => "Compressing...",
+        crate::progress::Progress::Uploading => "Uploading...",
+    }
+}
+
+fn queue_message(status: &crate::vrchat_queue::VrchatQueueStatus) -> String {
+    match status {
+        crate::vrchat_queue::VrchatQueueStatus::Queued(ahead) => {
+            format!("Waiting for {} VRChat request(s) ahead...", ahead)
+        }
+        crate::vrchat_queue::VrchatQueueStatus::WaitingForRateLimit(ahead) => format!(
+            "Waiting for the VRChat rate limit ({} request(s) ahead)...",
+            ahead
+        ),
+        crate::vrchat_queue::VrchatQueueStatus::Sending => "Sending to VRChat...".to_string(),
+    }
+}
+
+// NOTE: Same location as Tauri's resource directory on Windows (next to the executable).
+fn bundled_ffmpeg_path() -> Result<String, AppError> {
+    let exe_path = std::env::current_exe().map_err(AppError::from_error_with_message(
+        "Failed to get executable path",
+    ))?;
+
+    let exe_dir = exe_path.parent().ok_or(AppError::Unknown(
+        "Executable has no parent directory".to_string(),
+    ))?;
+
+    Ok(exe_dir
+        .join("resources")
+        .join("ffmpeg.exe")
+        .to_string_lossy()
+        .into_owned())
+}
+
+/// Exit codes are part of the command-line interface. Do not renumber existing variants.
+pub fn exit_code(error: &AppError) -> i32 {
+    match error {
+        AppError::Unknown(_) => 1,
+        AppError::ConfigContents(_) => 10,
+        AppError::ConfigExistance(_) => 11,
+        AppError::ConfigDirectoryExistance(_) => 12,
+        AppError::UploaderAuthRequired(_) => 20,
+        AppError::VrchatAuthRequired(_) => 21,
+        AppError::VrchatPlusRequired(_) => 22,
+        AppError::VrchatRateLimited(_) => 23,
+    }
+}
+
+fn error_message(error: &AppError) -> String {
+    match error {
+        AppError::Unknown(message)
+        | AppError::ConfigContents(message)
+        | AppError::ConfigExistance(message)
+        | AppError::ConfigDirectoryExistance(message)
+        | AppError::UploaderAuthRequired(message)
+        | AppError::VrchatAuthRequired(message)
+        | AppError::VrchatPlusRequired(message)
+        | AppError::VrchatRateLimited(message) => format!("{} ({})", error, message),
+    }
+}
+
+fn json_result(
+    file: &str,
+    mode: &crate::SendRequestEventMode,
+    result: &Result<Option<String>, AppError>,
+) -> String {
+    let result = HeadlessResult {
+        file,
+        mode,
+        outcome: match result {
+            Ok(url) => crate::send::SendOutcome::Sent(url.clone()),
+            Err(error) => crate::send::SendOutcome::Failed(error.clone()),
+        },
+    };
+
+    serde_json::to_string(&result).expect("headless result is serializable")
+}
+
+// NOTE: Release builds use the Windows GUI subsystem and have no console. Reuse the caller's one so stdout reaches the shell.
+#[cfg(windows)]
+fn attach_parent_console() {
+    use windows::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS)
+            .unwrap_or_else(|e| debug!("Failed to attach parent console: {:?}", e));
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_parent_console() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaves_gui_launches_to_tauri() {
+        let args =
+            |args: &[&str]| -> Vec<String> { args.iter().map(|arg| arg.to_string()).collect() };
+
+        assert_eq!(run_command_line(&args(&["app"])), None);
+        assert_eq!(run_command_line(&args(&["app", "send", "a.png"])), None);
+        assert_eq!(
+            run_command_line(&args(&["app", "send", "--json", "a.png"])),
+            None
+        );
+        assert_eq!(
+            run_command_line(&args(&["app", "--version"])),
+            Some(EXIT_SUCCESS)
+        );
+        assert_eq!(
+            run_command_line(&args(&["app", "send", "--no-gui", "a.png"])),
+            Some(EXIT_USAGE)
+        );
+    }
+
+    #[test]
+    fn uses_distinct_exit_codes() {
+        let errors = [
+            AppError::Unknown(String::new()),
+            AppError::ConfigContents(String::new()),
+            AppError::ConfigExistance(String::new()),
+            AppError::ConfigDirectoryExistance(String::new()),
+            AppError::UploaderAuthRequired(String::new()),
+            AppError::VrchatAuthRequired(String::new()),
+            AppError::VrchatPlusRequired(String::new()),
+            AppError::VrchatRateLimited(String::new()),
+        ];
+
+        let codes: std::collections::HashSet<i32> = errors.iter().map(exit_code).collect();
+
+        assert_eq!(codes.len(), errors.len());
+        assert!(!codes.contains(&EXIT_SUCCESS));
+        assert!(!codes.contains(&EXIT_USAGE));
+    }
+
+    #[test]
+    fn formats_queue_status_for_humans() {
+        assert_eq!(
+            queue_message(&crate::vrchat_queue::VrchatQueueStatus::Queued(2)),
+            "Waiting for 2 VRChat request(s) ahead..."
+        );
+        assert_eq!(
+            queue_message(&crate::vrchat_queue::VrchatQueueStatus::Sending),
+            "Sending to VRChat..."
+        );
+    }
+
+    #[test]
+    fn formats_json_result() {
+        let mode = crate::SendRequestEventMode::UploadImageToImageServer;
+
+        let sent = json_result(
+            "a.png",
+            &mode,
+            &Ok(Some("https://example.com/a".to_string())),
+        );
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&sent).unwrap(),
+            serde_json::json!({
+                "file": "a.png",
+                "mode": "UploadImageToImageServer",
+                "outcome": { "type": "Sent", "content": "https://example.com/a" },
+            })
+        );
+
+        let failed = json_result(
+            "a.png",
+            &mode,
+            &Err(AppError::UploaderAuthRequired("no key".to_string())),
+        );
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&failed).unwrap()["outcome"]["content"],
+            serde_json::json!({ "type": "UploaderAuthRequired", "message": "no key" })
+        );
+    }
+}
@@ -0,0 +1,273 @@
+use tauri::Manager as _;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt as _, Shortcut, ShortcutState};
+use tauri_specta::Event as _;
+
+use crate::prelude::*;
+
+/// Registered shortcuts and their actions, to dispatch presses from the plugin handler.
+static REGISTERED_HOTKEYS: std::sync::Mutex<Vec<(Shortcut, HotkeyAction)>> =
+    std::sync::Mutex::new(Vec::new());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+pub enum HotkeyAction {
+    CaptureRegion,
+    CaptureRegionToVideoServer,
+    RepeatLastRegion,
+    SendClipboardImage,
+}
+
+impl HotkeyAction {
+    const ALL: [HotkeyAction; 4] = [
+        HotkeyAction::CaptureRegion,
+        HotkeyAction::CaptureRegionToVideoServer,
+        HotkeyAction::RepeatLastRegion,
+        HotkeyAction::SendClipboardImage,
+    ];
+
+    /// Config key holding the shortcut, e.g. `CommandOrControl+Shift+C`. Empty disables the hotkey.
+    fn config_key(self) -> &'static str {
+        match self {
+            HotkeyAction::CaptureRegion => "hotkey_capture_region",
+            HotkeyAction::CaptureRegionToVideoServer => "hotkey_capture_region_to_video_server",
+            HotkeyAction::RepeatLastRegion => "hotkey_repeat_last_region",
+            HotkeyAction::SendClipboardImage => "hotkey_send_clipboard_image",
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, specta::Type)]
+pub struct HotkeyStatus {
+    action: HotkeyAction,
+    shortcut: String,
+    /// Why the hotkey is not registered, `None` if it is.
+    error: Option<String>,
+}
+
+pub fn plugin() -> tauri::plugin::TauriPlugin<tauri::Wry> {
+    tauri_plugin_global_shortcut::Builder::new()
+        .with_handler(|app_handle, shortcut, event| {
+            if event.state() != ShortcutState::Pressed {
+                return;
+            }
+
+            let action = REGISTERED_HOTKEYS
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|(registered, _)| registered == shortcut)
+                .map(|(_, action)| *action);
+
+            if let Some(action) = action {
+                // NOTE: Actions may wait for windows to hide, so they must not block the event loop.
+                let app_handle = app_handle.clone();
+                std::thread::spawn(move || run_action(&app_handle, action));
+            }
+        })
+        .build()
+}
+
+pub fn init_hotkeys(app_handle: tauri::AppHandle) {
+    match register_hotkeys(app_handle) {
+        Ok(statuses) => {
+            for status in statuses {
+                match status.error {
+                    Some(error) => warn!(
+                        "Hotkey {} for {:?} is not registered: {}",
+                        status.shortcut, status.action, error
+                    ),
+                    None => info!(
+                        "Hotkey {} registered for {:?}",
+                        status.shortcut, status.action
+                    ),
+                }
+            }
+        }
+        Err(e) => error!("Failed to register hotkeys: {}", e),
+    }
+}
+
+/// Registers the hotkeys in the config again, replacing the previous ones. Call after changing them.
+#[tauri::command]
+#[specta::specta]
+pub fn register_hotkeys(app_handle: tauri::AppHandle) -> Result<Vec<HotkeyStatus>, String> {
+    let bindings = {
+        let state = app_handle.state::<crate::app_data::AppData>();
+
+        if !state.is_healthy() {
+            return Err("Config is not loaded".to_string());
+        }
+
+        let config = state.lock_config();
+
+        HotkeyAction::ALL
+            .iter()
+            .filter_map(|action| {
+                let shortcut = config.get(action.config_key())?.trim();
+                (!shortcut.is_empty()).then(|| (*action, shortcut.to_string()))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let global_shortcut = app_handle.global_shortcut();
+
+    global_shortcut
+        .unregister_all()
+        .map_err(|e| format!("Failed to unregister hotkeys: {e}"))?;
+
+    let mut registered = Vec::new();
+
+    let statuses = bindings
+        .iter()
+        .zip(check_bindings(&bindings))
+        .map(|((action, shortcut), checked)| {
+            let error = checked.and_then(|parsed| {
+                global_shortcut.register(parsed).map_err(|e| {
+                    format!("Failed to register, it may be used by another application: {e}")
+                })?;
+
+                registered.push((parsed, *action));
+                Ok(())
+            });
+
+            HotkeyStatus {
+                action: *action,
+                shortcut: shortcut.clone(),
+                error: error.err(),
+            }
+        })
+        .collect();
+
+    *REGISTERED_HOTKEYS.lock().unwrap() = registered;
+
+    Ok(statuses)
+}
+
+/// Parses the bindings in order. A shortcut already bound to an earlier action is a conflict.
+fn check_bindings(bindings: &[(HotkeyAction, String)]) -> Vec<Result<Shortcut, String>> {
+    let mut checked: Vec<Result<Shortcut, String>> = Vec::new();
+
+    for (_, shortcut) in bindings {
+        let result = shortcut
+            .parse::<Shortcut>()
+            .map_err(|e| format!("Invalid shortcut: {e}"))
+            .and_then(|parsed| {
+                match checked
+                    .iter()
+                    .position(|earlier| earlier.as_ref().ok() == Some(&parsed))
+                {
+                    Some(earlier) => Err(format!("Conflicts with {:?}", bindings[earlier].0)),
+                    None => Ok(parsed),
+                }
+            });
+
+        checked.push(result);
+    }
+
+    checked
+}
+
+fn run_action(app_handle: &tauri::AppHandle, action: HotkeyAction) {
+    info!("Hotkey pressed: {:?}", action);
+
+    let result = match action {
+        HotkeyAction::CaptureRegion => crate::capture::start_capture(),
+        HotkeyAction::CaptureRegionToVideoServer => crate::capture::start_capture_with_action(
+            crate::capture::FinishCaptureNextAction::UploadImageToVideoServer,
+        ),
+        HotkeyAction::RepeatLastRegion => {
+            // NOTE: Shortcuts are handled on the event loop, which capturing must not block.
+            let app_handle = app_handle.clone();
+
+            tauri::async_runtime::spawn_blocking(move || {
+                if let Err(e) = crate::capture::repeat_last_region(&app_handle) {
+                    error!("Hotkey action {:?} failed: {}", action, e);
+                }
+            });
+
+            Ok(())
+        }
+        HotkeyAction::SendClipboardImage => request_clipboard_image_send(app_handle),
+    };
+
+    if let Err(e) = result {
+        error!("Hotkey action {:?} failed: {}", action, e);
+    }
+}
+
+/// Saves the clipboard image, keeps a copy in the auto-save folder if enabled, and asks where to send it.
+fn request_clipboard_image_send(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let png = crate::clipboard::read_clipboard_image(app_handle)
+        .map_err(|e| format!("Failed to read clipboard image: {e}"))?;
+
+    let file = crate::jobs::save_image_bytes_to_workspace(&png)
+        .map_err(|e| format!("Failed to save clipboard image: {e}"))?;
+
+    let saved_files = crate::capture::auto_save::AutoSaver::load(
+        app_handle,
+        "clipboard",
+        &crate::capture::FinishCaptureNextAction::Ask,
+    )
+    .and_then(|auto_saver| match image::load_from_memory(&png) {
+        Ok(image) => Some(auto_saver.save(None, &image)),
+        Err(e) => {
+            error!("Failed to decode clipboard image to auto-save: {}", e);
+            None
+        }
+    })
+    .unwrap_or_default();
+
+    crate::SendRequestEvent {
+        file: file.to_string_lossy().to_string(),
+        mode: None,
+        preselected_mode: None,
+        copy: None,
+        saved_files,
+    }
+    .emit(app_handle)
+    .map_err(|e| format!("Failed to emit send_request event: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bindings(shortcuts: &[(HotkeyAction, &str)]) -> Vec<(HotkeyAction, String)> {
+        shortcuts
+            .iter()
+            .map(|(action, shortcut)| (*action, shortcut.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn detects_invalid_and_conflicting_shortcuts() {
+        let checked = check_bindings(&bindings(&[
+            (HotkeyAction::CaptureRegion, "Ctrl+Shift+C"),
+            (HotkeyAction::CaptureRegionToVideoServer, "Shift+Ctrl+KeyC"),
+            (HotkeyAction::RepeatLastRegion, "Ctrl+Shift+NotAKey"),
+            (HotkeyAction::SendClipboardImage, "Alt+V"),
+        ]));
+
+        assert_eq!(
+            checked[0],
+            "Ctrl+Shift+C"
+                .parse::<Shortcut>()
+                .map_err(|_| String::new())
+        );
+        assert_eq!(checked[1], Err("Conflicts with CaptureRegion".to_string()));
+        assert!(checked[2]
+            .as_ref()
+            .unwrap_err()
+            .starts_with("Invalid shortcut"));
+        assert!(checked[3].is_ok());
+    }
+
+    #[test]
+    fn uses_a_config_key_per_action() {
+        let mut keys = HotkeyAction::ALL.map(HotkeyAction::config_key).to_vec();
+        keys.sort();
+        keys.dedup();
+
+        assert_eq!(keys.len(), HotkeyAction::ALL.len());
+        assert!(keys.iter().all(|key| key.starts_with("hotkey_")));
+    }
+}
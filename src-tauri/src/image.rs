@@ -5,6 +5,11 @@ use crate::prelude::*;
 #[tauri::command]
 #[specta::specta]
 pub async fn is_able_to_read_image_file(file_path: &str) -> Result<bool, ()> {
+    // NOTE: URLs are only checked with a HEAD request here. Downloading would fetch them twice.
+    if crate::remote_image::is_remote_url(file_path) {
+        return Ok(crate::remote_image::is_remote_image(file_path).await);
+    }
+
     Ok(image::open(file_path).is_ok())
 }
 
@@ -80,3 +85,325 @@ pub fn resize_image(
 
     Ok(())
 }
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, specta::Type)]
+pub struct ScreenshotMetadata {
+    pub world_name: Option<String>,
+    pub world_id: Option<String>,
+    pub instance_id: Option<String>,
+    pub author: Option<String>,
+    pub players: Vec<String>,
+}
+
+impl ScreenshotMetadata {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Reads world and player information that VRChat (XMP) or VRCX (JSON description) embedded in a PNG screenshot.
+#[tauri::command]
+#[specta::specta]
+pub async fn read_screenshot_metadata(
+    file_path: &str,
+) -> Result<Option<ScreenshotMetadata>, AppError> {
+    let bytes = std::fs::read(file_path).map_err(AppError::from_error_with_message(
+        "Failed to read image file",
+    ))?;
+
+    Ok(screenshot_metadata_from_png(&bytes))
+}
+
+pub fn screenshot_metadata_from_png(bytes: &[u8]) -> Option<ScreenshotMetadata> {
+    let mut metadata = ScreenshotMetadata::default();
+
+    for (keyword, text) in read_png_text_chunks(bytes) {
+        let parsed = match keyword.as_str() {
+            "XML:com.adobe.xmp" => screenshot_metadata_from_xmp(&text),
+            "Description" => screenshot_metadata_from_vrcx_json(&text),
+            _ => continue,
+        };
+
+        // NOTE: VRCX knows the instance and players, VRChat does not. Keep whatever each source knows.
+        metadata = ScreenshotMetadata {
+            world_name: metadata.world_name.or(parsed.world_name),
+            world_id: metadata.world_id.or(parsed.world_id),
+            instance_id: metadata.instance_id.or(parsed.instance_id),
+            author: metadata.author.or(parsed.author),
+            players: if metadata.players.is_empty() {
+                parsed.players
+            } else {
+                metadata.players
+            },
+        };
+    }
+
+    if metadata.is_empty() {
+        return None;
+    }
+
+    Some(metadata)
+}
+
+const PNG_SIGNATURE: &[u8] = &[0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+// NOTE: Only uncompressed tEXt and iTXt chunks are read. Screenshot tools do not compress their metadata.
+fn read_png_text_chunks(bytes: &[u8]) -> Vec<(String, String)> {
+    let mut chunks = Vec::new();
+
+    let Some(mut rest) = bytes.strip_prefix(PNG_SIGNATURE) else {
+        return chunks;
+    };
+
+    while rest.len() >= 12 {
+        let length = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+        let chunk_type = &rest[4..8];
+
+        let Some(data) = rest.get(8..8 + length) else {
+            break;
+        };
+
+        match chunk_type {
+            b"tEXt" => {
+                if let Some((keyword, text)) = split_once_nul(data) {
+                    // tEXt is Latin-1
+                    chunks.push((
+                        text_from_latin1(keyword),
+                        text.iter().map(|&c| c as char).collect(),
+                    ));
+                }
+            }
+            b"iTXt" => {
+                if let Some(chunk) = parse_itxt_chunk(data) {
+                    chunks.push(chunk);
+                }
+            }
+            b"IEND" => break,
+            _ => {}
+        }
+
+        rest = rest.get(12 + length..).unwrap_or_default();
+    }
+
+    chunks
+}
+
+fn parse_itxt_chunk(data: &[u8]) -> Option<(String, String)> {
+    let (keyword, rest) = split_once_nul(data)?;
+    let (&compression_flag, rest) = rest.split_first()?;
+
+    if compression_flag != 0 {
+        debug!(
+            "Skipping compressed iTXt chunk: {}",
+            text_from_latin1(keyword)
+        );
+        return None;
+    }
+
+    // compression method, language tag, translated keyword
+    let (_language_tag, rest) = split_once_nul(rest.get(1..)?)?;
+    let (_translated_keyword, text) = split_once_nul(rest)?;
+
+    Some((
+        text_from_latin1(keyword),
+        String::from_utf8_lossy(text).into_owned(),
+    ))
+}
+
+fn split_once_nul(data: &[u8]) -> Option<(&[u8], &[u8])> {
+    let position = data.iter().position(|&c| c == 0)?;
+
+    Some((&data[..position], &data[position + 1..]))
+}
+
+fn text_from_latin1(data: &[u8]) -> String {
+    data.iter().map(|&c| c as char).collect()
+}
+
+fn screenshot_metadata_from_xmp(xmp: &str) -> ScreenshotMetadata {
+    ScreenshotMetadata {
+        world_name: xmp_property(xmp, "vrc:WorldDisplayName"),
+        world_id: xmp_property(xmp, "vrc:WorldID"),
+        instance_id: xmp_property(xmp, "vrc:InstanceID"),
+        author: xmp_property(xmp, "xmp:Author"),
+        players: Vec::new(),
+    }
+}
+
+// Supports both `<name>value</name>` and `name="value"` forms.
+fn xmp_property(xmp: &str, name: &str) -> Option<String> {
+    let element_start = format!("<{}>", name);
+    let element_end = format!("</{}>", name);
+    let attribute_start = format!("{}=\"", name);
+
+    let value = if let Some(start) = xmp.find(&element_start) {
+        let value = &xmp[start + element_start.len()..];
+        &value[..value.find(&element_end)?]
+    } else {
+        let start = xmp.find(&attribute_start)?;
+        let value = &xmp[start + attribute_start.len()..];
+        &value[..value.find('"')?]
+    };
+
+    let value = value
+        .trim()
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&");
+
+    if value.is_empty() {
+        return None;
+    }
+
+    Some(value)
+}
+
+fn screenshot_metadata_from_vrcx_json(description: &str) -> ScreenshotMetadata {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(description) else {
+        return ScreenshotMetadata::default();
+    };
+
+    let string_at = |pointer: &str| {
+        json.pointer(pointer)
+            .and_then(|value| value.as_str())
+            .filter(|value| !value.is_empty())
+            .map(|value| value.to_string())
+    };
+
+    let players = json
+        .get("players")
+        .and_then(|players| players.as_array())
+        .map(|players| {
+            players
+                .iter()
+                .filter_map(|player| player.get("displayName")?.as_str())
+                .map(|name| name.to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    ScreenshotMetadata {
+        world_name: string_at("/world/name"),
+        world_id: string_at("/world/id"),
+        instance_id: string_at("/world/instanceId"),
+        author: string_at("/author/displayName"),
+        players,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(chunk_type: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut chunk = Vec::new();
+        chunk.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        chunk.extend_from_slice(chunk_type);
+        chunk.extend_from_slice(data);
+        chunk.extend_from_slice(&[0, 0, 0, 0]); // CRC is not checked
+        chunk
+    }
+
+    fn png_with_chunks(chunks: &[Vec<u8>]) -> Vec<u8> {
+        let mut png = PNG_SIGNATURE.to_vec();
+        png.extend(chunk(b"IHDR", &[0; 13]));
+        for c in chunks {
+            png.extend_from_slice(c);
+        }
+        png.extend(chunk(b"IEND", &[]));
+        png
+    }
+
+    fn itxt(keyword: &str, text: &str) -> Vec<u8> {
+        let mut data = keyword.as_bytes().to_vec();
+        data.extend_from_slice(&[0, 0, 0, 0, 0]);
+        data.extend_from_slice(text.as_bytes());
+        chunk(b"iTXt", &data)
+    }
+
+    const VRCHAT_XMP: &str = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/"><rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"><rdf:Description xmlns:xmp="http://ns.adobe.com/xap/1.0/" xmlns:vrc="http://ns.vrchat.com/vrc/1.0/"><xmp:CreatorTool>VRChat</xmp:CreatorTool><xmp:Author>Neko</xmp:Author><vrc:WorldID>wrld_4cf554b4-430c-4f8f-b53e-1f294eed230b</vrc:WorldID><vrc:WorldDisplayName>The Black Cat &amp; Friends</vrc:WorldDisplayName></rdf:Description></rdf:RDF></x:xmpmeta>"#;
+
+    const VRCX_DESCRIPTION: &str = r#"{"application":"VRCX","version":1,"author":{"id":"usr_1","displayName":"Neko"},"world":{"name":"The Black Cat","id":"wrld_4cf554b4-430c-4f8f-b53e-1f294eed230b","instanceId":"wrld_4cf554b4-430c-4f8f-b53e-1f294eed230b:12345~region(jp)"},"players":[{"id":"usr_1","displayName":"Neko"},{"id":"usr_2","displayName":"Inu"}]}"#;
+
+    #[test]
+    fn reads_vrchat_xmp() {
+        let metadata = screenshot_metadata_from_png(&png_with_chunks(&[itxt(
+            "XML:com.adobe.xmp",
+            VRCHAT_XMP,
+        )]))
+        .unwrap();
+
+        assert_eq!(
+            metadata.world_name.as_deref(),
+            Some("The Black Cat & Friends")
+        );
+        assert_eq!(
+            metadata.world_id.as_deref(),
+            Some("wrld_4cf554b4-430c-4f8f-b53e-1f294eed230b")
+        );
+        assert_eq!(metadata.author.as_deref(), Some("Neko"));
+        assert!(metadata.players.is_empty());
+    }
+
+    #[test]
+    fn reads_vrcx_description() {
+        let mut data = b"Description\0".to_vec();
+        data.extend_from_slice(VRCX_DESCRIPTION.as_bytes());
+
+        let metadata =
+            screenshot_metadata_from_png(&png_with_chunks(&[chunk(b"tEXt", &data)])).unwrap();
+
+        assert_eq!(metadata.world_name.as_deref(), Some("The Black Cat"));
+        assert_eq!(
+            metadata.instance_id.as_deref(),
+            Some("wrld_4cf554b4-430c-4f8f-b53e-1f294eed230b:12345~region(jp)")
+        );
+        assert_eq!(metadata.players, vec!["Neko", "Inu"]);
+    }
+
+    #[test]
+    fn merges_vrchat_and_vrcx_metadata() {
+        let metadata = screenshot_metadata_from_png(&png_with_chunks(&[
+            itxt("XML:com.adobe.xmp", VRCHAT_XMP),
+            itxt("Description", VRCX_DESCRIPTION),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            metadata.world_name.as_deref(),
+            Some("The Black Cat & Friends")
+        );
+        assert!(metadata.instance_id.is_some());
+        assert_eq!(metadata.players.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn accepts_urls_without_downloading() {
+        let input_file =
+            std::env::var("CARGO_MANIFEST_DIR").unwrap() + "/test_data/input_image.png";
+
+        assert_eq!(is_able_to_read_image_file(&input_file).await, Ok(true));
+        assert_eq!(
+            is_able_to_read_image_file("https://example.invalid/a.png").await,
+            Ok(true)
+        );
+        assert_eq!(
+            is_able_to_read_image_file("/nonexistent/a.png").await,
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn returns_none_without_metadata() {
+        let input_file =
+            std::env::var("CARGO_MANIFEST_DIR").unwrap() + "/test_data/input_image.png";
+
+        assert_eq!(
+            screenshot_metadata_from_png(&std::fs::read(input_file).unwrap()),
+            None
+        );
+        assert_eq!(screenshot_metadata_from_png(b"not a png"), None);
+    }
+}
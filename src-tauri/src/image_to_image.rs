@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use tauri::Manager as _;
 
 #[tauri::command]
 #[specta::specta]
@@ -10,36 +11,55 @@ pub async fn upload_image_to_image_server(
 ) -> Result<String, AppError> {
     let progress_callback = Some(progress::create_progress_callback(&handle));
 
-    upload_image_to_image_server_internal(
-        file_path,
+    let input = crate::remote_image::resolve_input(file_path).await?;
+
+    let workspace = crate::jobs::create_workspace()?;
+
+    let url = upload_image_to_image_server_internal(
+        input.path(),
         api_key,
         uploader_base_url,
+        &workspace,
         progress_callback.as_ref(),
     )
-    .await
+    .await;
+
+    crate::jobs::remove_workspace(&workspace);
+
+    let url = url?;
+
+    crate::send::remember_sent_file(file_path);
+
+    let state = handle.state::<crate::app_data::AppData>();
+
+    // NOTE: The upload already succeeded, so a broken config only skips the chatbox.
+    if state.is_healthy() {
+        crate::osc::post_url_to_chatbox(&state.lock_config(), &url);
+    }
+
+    Ok(url)
 }
 
 pub async fn upload_image_to_image_server_internal(
     file_path: &str,
     api_key: &str,
     uploader_base_url: &str,
+    workspace: &std::path::Path,
     progress_callback: Option<&progress::ProgressCallback>,
 ) -> Result<String, AppError> {
     if let Some(cb) = progress_callback {
         cb(progress::Progress::Starting)
     }
-    let resized_image_path = crate::file::temp_file_path("resized_image.png")
+    let resized_image_path = workspace
+        .join("resized_image.png")
         .to_string_lossy()
         .into_owned();
 
-    let (width, height) = (1920, 1920);
-
     if let Some(cb) = progress_callback {
         cb(progress::Progress::Compressing)
     }
 
-    crate::image::resize_image(file_path, &resized_image_path, width, height)
-        .map_err(AppError::from_error_with_message("Failed to resize image"))?;
+    render_image_server_image(file_path, &resized_image_path)?;
 
     if let Some(cb) = progress_callback {
         cb(progress::Progress::Uploading)
@@ -50,6 +70,13 @@ pub async fn upload_image_to_image_server_internal(
     Ok(url)
 }
 
+pub fn render_image_server_image(file_path: &str, output_path: &str) -> Result<(), AppError> {
+    let (width, height) = (1920, 1920);
+
+    crate::image::resize_image(file_path, output_path, width, height)
+        .map_err(AppError::from_error_with_message("Failed to resize image"))
+}
+
 async fn upload_image_file_to_image_server(
     image_file_path: &str,
     api_key: &str,
@@ -9,6 +9,41 @@ pub async fn upload_image_to_video_server(
     api_key: &str,
     uploader_base_url: &str,
 ) -> Result<String, AppError> {
+    let ffmpeg_path = resolve_ffmpeg_path(&handle)?;
+
+    let progress_callback = Some(progress::create_progress_callback(&handle));
+
+    let input = crate::remote_image::resolve_input(file_path).await?;
+
+    let workspace = crate::jobs::create_workspace()?;
+
+    let url = upload_image_to_video_server_internal(
+        &ffmpeg_path,
+        input.path(),
+        api_key,
+        uploader_base_url,
+        &workspace,
+        progress_callback.as_ref(),
+    )
+    .await;
+
+    crate::jobs::remove_workspace(&workspace);
+
+    let url = url?;
+
+    crate::send::remember_sent_file(file_path);
+
+    let state = handle.state::<crate::app_data::AppData>();
+
+    // NOTE: The upload already succeeded, so a broken config only skips the chatbox.
+    if state.is_healthy() {
+        crate::osc::post_url_to_chatbox(&state.lock_config(), &url);
+    }
+
+    Ok(url)
+}
+
+pub fn resolve_ffmpeg_path(handle: &tauri::AppHandle) -> Result<String, AppError> {
     let ffmpeg_path = handle
         .path()
         .resolve("resources/ffmpeg.exe", tauri::path::BaseDirectory::Resource)
@@ -18,16 +53,7 @@ pub async fn upload_image_to_video_server(
         .to_string_lossy()
         .into_owned();
 
-    let progress_callback = Some(progress::create_progress_callback(&handle));
-
-    upload_image_to_video_server_internal(
-        &ffmpeg_path,
-        file_path,
-        api_key,
-        uploader_base_url,
-        progress_callback.as_ref(),
-    )
-    .await
+    Ok(ffmpeg_path)
 }
 
 pub async fn upload_image_to_video_server_internal(
@@ -35,30 +61,28 @@ pub async fn upload_image_to_video_server_internal(
     file_path: &str,
     api_key: &str,
     uploader_base_url: &str,
+    workspace: &std::path::Path,
     progress_callback: Option<&progress::ProgressCallback>,
 ) -> Result<String, AppError> {
     if let Some(cb) = progress_callback {
         cb(progress::Progress::Starting)
     }
-    let letterboxed_image_path = crate::file::temp_file_path("letterboxed_image.png")
+    let letterboxed_image_path = workspace
+        .join("letterboxed_image.png")
         .to_string_lossy()
         .into_owned();
 
-    let output_video_path = crate::file::temp_file_path("output.mp4")
-        .to_string_lossy()
-        .into_owned();
+    let output_video_path = workspace.join("output.mp4").to_string_lossy().into_owned();
 
     info!("FFmpeg path: {}", ffmpeg_path);
     debug!("Input image path: {}", file_path);
     debug!("Letterboxed image path: {}", letterboxed_image_path);
     debug!("Output video path: {}", output_video_path);
 
-    let (width, height) = (1280, 720);
-
     if let Some(cb) = progress_callback {
         cb(progress::Progress::Compressing)
     }
-    crate::image::resize_image_letterboxed(file_path, &letterboxed_image_path, width, height)?;
+    render_video_frame(file_path, &letterboxed_image_path)?;
     encode_image_to_video(ffmpeg_path, &letterboxed_image_path, &output_video_path).await?;
 
     if let Some(cb) = progress_callback {
@@ -69,6 +93,12 @@ pub async fn upload_image_to_video_server_internal(
     Ok(url)
 }
 
+pub fn render_video_frame(file_path: &str, output_path: &str) -> Result<(), AppError> {
+    let (width, height) = (1280, 720);
+
+    crate::image::resize_image_letterboxed(file_path, output_path, width, height)
+}
+
 async fn upload_video_to_video_server(
     video_file_path: &str,
     api_key: &str,
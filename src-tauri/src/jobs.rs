@@ -0,0 +1,340 @@
+use crate::prelude::*;
+
+// NOTE: Older jobs are dropped together with their workspace.
+const MAX_JOBS: usize = 100;
+// NOTE: Images that are not sent as jobs (clipboard, stdin) have no job to remove their workspace.
+const WORKSPACE_LIFETIME: std::time::Duration = std::time::Duration::from_secs(24 * 60 * 60);
+
+static JOBS: std::sync::Mutex<JobRegistry> = std::sync::Mutex::new(JobRegistry::new());
+static WORKSPACE_COUNTER: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
+
+#[derive(Debug, Clone, serde::Serialize, specta::Type)]
+#[serde(tag = "type", content = "content")]
+pub enum JobStatus {
+    Running,
+    Succeeded(Option<String>),
+    Failed(AppError),
+}
+
+#[derive(Debug, Clone, serde::Serialize, specta::Type)]
+pub struct Job {
+    pub id: u32,
+    pub file: String,
+    pub mode: crate::SendRequestEventMode,
+    /// From the screenshot metadata, so history entries can be grouped by world.
+    pub world_name: Option<String>,
+    pub world_id: Option<String>,
+    pub status: JobStatus,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+}
+
+struct JobRegistry {
+    next_id: u32,
+    jobs: std::collections::VecDeque<Job>,
+}
+
+impl JobRegistry {
+    pub const fn new() -> Self {
+        Self {
+            next_id: 1,
+            jobs: std::collections::VecDeque::new(),
+        }
+    }
+
+    pub fn start(
+        &mut self,
+        file: &str,
+        mode: &crate::SendRequestEventMode,
+        metadata: Option<crate::image::ScreenshotMetadata>,
+    ) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let metadata = metadata.unwrap_or_default();
+
+        self.jobs.push_back(Job {
+            id,
+            file: file.to_string(),
+            mode: mode.clone(),
+            world_name: metadata.world_name,
+            world_id: metadata.world_id,
+            status: JobStatus::Running,
+            started_at: now(),
+            finished_at: None,
+        });
+
+        while self.jobs.len() > MAX_JOBS {
+            if let Some(job) = self.jobs.pop_front() {
+                remove_workspace_of(&job.file);
+            }
+        }
+
+        id
+    }
+
+    pub fn finish(&mut self, id: u32, result: &Result<Option<String>, AppError>) {
+        let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) else {
+            return;
+        };
+
+        job.status = match result {
+            Ok(url) => JobStatus::Succeeded(url.clone()),
+            Err(error) => JobStatus::Failed(error.clone()),
+        };
+        job.finished_at = Some(now());
+    }
+
+    pub fn get(&self, id: u32) -> Option<Job> {
+        self.jobs.iter().find(|job| job.id == id).cloned()
+    }
+
+    /// Newest first.
+    pub fn history(&self) -> Vec<Job> {
+        self.jobs.iter().rev().cloned().collect()
+    }
+}
+
+fn now() -> String {
+    chrono::Utc::now()
+        .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+        .to_string()
+}
+
+// NOTE: URLs are not read here. Only local screenshots carry the world into the history.
+pub fn start_job(file: &str, mode: &crate::SendRequestEventMode) -> u32 {
+    let metadata = std::fs::read(file)
+        .ok()
+        .and_then(|bytes| crate::image::screenshot_metadata_from_png(&bytes));
+
+    JOBS.lock().unwrap().start(file, mode, metadata)
+}
+
+pub fn finish_job(id: u32, result: &Result<Option<String>, AppError>) {
+    JOBS.lock().unwrap().finish(id, result);
+}
+
+pub fn job(id: u32) -> Option<Job> {
+    JOBS.lock().unwrap().get(id)
+}
+
+pub fn history() -> Vec<Job> {
+    JOBS.lock().unwrap().history()
+}
+
+fn workspace_root() -> std::path::PathBuf {
+    crate::file::temp_file_path("send-to-vrc-jobs")
+}
+
+/// Creates an empty directory for files that only exist for one job (uploaded bytes, downloads).
+pub fn create_workspace() -> Result<std::path::PathBuf, AppError> {
+    remove_expired_workspaces(
+        &workspace_root(),
+        std::time::SystemTime::now(),
+        &JOBS.lock().unwrap(),
+    );
+
+    let counter = WORKSPACE_COUNTER.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+
+    let workspace = workspace_root().join(format!(
+        "{}-{}-{}",
+        chrono::Utc::now().format("%Y%m%d%H%M%S"),
+        std::process::id(),
+        counter
+    ));
+
+    std::fs::create_dir_all(&workspace).map_err(AppError::from_error_with_message(
+        "Failed to create job workspace",
+    ))?;
+
+    Ok(workspace)
+}
+
+/// Writes image bytes into a new workspace, named after the detected format so the pipelines can read it.
+pub fn save_image_bytes_to_workspace(bytes: &[u8]) -> Result<std::path::PathBuf, AppError> {
+    let format = image::guess_format(bytes)
+        .map_err(AppError::from_error_with_message("Unsupported image data"))?;
+
+    let extension = format.extensions_str().first().unwrap_or(&"img");
+
+    let path = create_workspace()?.join(format!("input.{}", extension));
+
+    std::fs::write(&path, bytes).map_err(AppError::from_error_with_message(
+        "Failed to write image to job workspace",
+    ))?;
+
+    Ok(path)
+}
+
+/// Removes a workspace from `create_workspace` once nothing refers to its files.
+pub fn remove_workspace(workspace: &std::path::Path) {
+    std::fs::remove_dir_all(workspace)
+        .unwrap_or_else(|e| debug!("Failed to remove job workspace: {:?}", e));
+}
+
+/// Removes the workspace `file` is in. Files outside workspaces are left alone.
+pub fn remove_workspace_of(file: &str) {
+    let Some(workspace) = std::path::Path::new(file).parent() else {
+        return;
+    };
+
+    if workspace.parent() != Some(workspace_root().as_path()) {
+        return;
+    }
+
+    remove_workspace(workspace);
+}
+
+/// Removes workspaces older than `WORKSPACE_LIFETIME`, except the ones jobs in `registry` still refer to.
+fn remove_expired_workspaces(
+    root: &std::path::Path,
+    now: std::time::SystemTime,
+    registry: &JobRegistry,
+) {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let workspace = entry.path();
+
+        let is_expired = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .is_ok_and(|modified| {
+                now.duration_since(modified)
+                    .is_ok_and(|age| age > WORKSPACE_LIFETIME)
+            });
+
+        let has_job = registry
+            .jobs
+            .iter()
+            .any(|job| std::path::Path::new(&job.file).parent() == Some(workspace.as_path()));
+
+        if is_expired && !has_job {
+            std::fs::remove_dir_all(&workspace)
+                .unwrap_or_else(|e| debug!("Failed to remove expired workspace: {:?}", e));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODE: crate::SendRequestEventMode = crate::SendRequestEventMode::UploadImageToImageServer;
+
+    #[test]
+    fn tracks_job_status() {
+        let mut registry = JobRegistry::new();
+
+        let succeeded = registry.start("a.png", &MODE, None);
+        let failed = registry.start("b.png", &MODE, None);
+
+        assert!(matches!(
+            registry.get(succeeded).unwrap().status,
+            JobStatus::Running
+        ));
+
+        registry.finish(succeeded, &Ok(Some("https://example.com/a".to_string())));
+        registry.finish(failed, &Err(AppError::Unknown("boom".to_string())));
+
+        assert!(matches!(
+            registry.get(succeeded).unwrap().status,
+            JobStatus::Succeeded(Some(url)) if url == "https://example.com/a"
+        ));
+        assert!(registry.get(failed).unwrap().finished_at.is_some());
+        assert!(registry.get(failed + 1).is_none());
+
+        let history: Vec<u32> = registry.history().iter().map(|job| job.id).collect();
+        assert_eq!(history, vec![failed, succeeded]);
+    }
+
+    #[test]
+    fn records_world_of_screenshots() {
+        let mut registry = JobRegistry::new();
+
+        let id = registry.start(
+            "VRChat_2024-01-01_00-00-00.000_1920x1080.png",
+            &MODE,
+            Some(crate::image::ScreenshotMetadata {
+                world_name: Some("The Black Cat".to_string()),
+                world_id: Some("wrld_4cf554b4-430c-4f8f-b53e-1f294eed230b".to_string()),
+                ..Default::default()
+            }),
+        );
+        let without_metadata = registry.start("photo.png", &MODE, None);
+
+        let job = registry.get(id).unwrap();
+        assert_eq!(job.world_name.as_deref(), Some("The Black Cat"));
+        assert_eq!(
+            job.world_id.as_deref(),
+            Some("wrld_4cf554b4-430c-4f8f-b53e-1f294eed230b")
+        );
+        assert!(registry.get(without_metadata).unwrap().world_name.is_none());
+    }
+
+    #[test]
+    fn drops_old_jobs_with_their_workspace() {
+        let mut registry = JobRegistry::new();
+
+        let workspace = create_workspace().unwrap();
+        let file = workspace.join("upload.png");
+        std::fs::write(&file, b"").unwrap();
+
+        let first = registry.start(&file.to_string_lossy(), &MODE, None);
+
+        for _ in 0..MAX_JOBS {
+            registry.start("other.png", &MODE, None);
+        }
+
+        assert!(registry.get(first).is_none());
+        assert_eq!(registry.history().len(), MAX_JOBS);
+        assert!(!workspace.exists());
+    }
+
+    #[test]
+    fn saves_image_bytes_with_detected_extension() {
+        let input_file =
+            std::env::var("CARGO_MANIFEST_DIR").unwrap() + "/test_data/input_image.png";
+
+        let path = save_image_bytes_to_workspace(&std::fs::read(input_file).unwrap()).unwrap();
+
+        assert_eq!(path.extension().unwrap(), "png");
+        assert!(image::open(&path).is_ok());
+
+        assert!(save_image_bytes_to_workspace(b"not an image").is_err());
+    }
+
+    #[test]
+    fn removes_expired_workspaces_without_jobs() {
+        let root = crate::file::temp_file_path("send-to-vrc-jobs-test-expired");
+        let without_job = root.join("without-job");
+        let with_job = root.join("with-job");
+        std::fs::create_dir_all(&without_job).unwrap();
+        std::fs::create_dir_all(&with_job).unwrap();
+
+        let mut registry = JobRegistry::new();
+        registry.start(&with_job.join("upload.png").to_string_lossy(), &MODE, None);
+
+        remove_expired_workspaces(&root, std::time::SystemTime::now(), &registry);
+        assert!(without_job.exists());
+
+        let later = std::time::SystemTime::now() + WORKSPACE_LIFETIME * 2;
+        remove_expired_workspaces(&root, later, &registry);
+        assert!(!without_job.exists());
+        assert!(with_job.exists());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn keeps_files_outside_workspaces() {
+        let dir = crate::file::temp_file_path("send-to-vrc-jobs-test-outside");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        remove_workspace_of(&dir.join("photo.png").to_string_lossy());
+
+        assert!(dir.exists());
+    }
+}
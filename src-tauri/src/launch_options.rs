@@ -1,14 +1,62 @@
+use std::io::Read as _;
+
 use crate::prelude::*;
 
-#[derive(specta::Type, serde::Serialize, PartialEq, Eq, Debug)]
+pub const USAGE: &str = "Usage:
+  send-to-vrc [OPTIONS] [FILE]...
+  send-to-vrc send [OPTIONS] <FILE>...
+  send-to-vrc capture
+
+Options:
+  --mode <video|image|print>  Send without asking where to send
+  --copy, --no-copy           Override copying the URL after upload
+  --profile <NAME>            Use config.<NAME>.ini and a separate instance
+  --no-gui                    Send without opening a window (requires --mode)
+  --json                      Print the result as JSON (requires --no-gui)
+  -h, --help                  Print this help
+  -V, --version               Print the version
+
+Several files are sent one after another.
+FILE may also be an http(s) URL of an image, which is downloaded first,
+or - to read an image from standard input.";
+
+/// File argument that reads the image from standard input.
+pub const STDIN_FILE: &str = "-";
+
+const MAX_STDIN_BYTES: u64 = 50 * 1024 * 1024;
+
+// NOTE: Standard input can only be read once, but launch options are parsed more than once.
+static STDIN_IMAGE: std::sync::OnceLock<Result<String, AppError>> = std::sync::OnceLock::new();
+
+#[derive(specta::Type, serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug)]
 #[serde(tag = "type", content = "args")]
 pub enum LaunchOptionsMode {
     Default,
-    Send { file: String },
+    Send {
+        files: Vec<String>,
+        options: SendOptions,
+    },
     Capture,
+    Help,
+    Version,
 }
 
-#[derive(specta::Type, serde::Serialize, Debug)]
+#[derive(specta::Type, serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Default)]
+pub struct SendOptions {
+    pub mode: Option<crate::SendRequestEventMode>,
+    /// Highlighted on the send page until the user confirms it. Deep links use this instead of
+    /// `mode`, because any web page can open them.
+    pub preselected_mode: Option<crate::SendRequestEventMode>,
+    /// Overrides `should_copy_after_upload` when set.
+    pub copy: Option<bool>,
+    /// Applied by the backend at startup, see `config::set_profile`.
+    pub profile: Option<String>,
+    /// Handled by `headless` before Tauri starts, so the frontend never sees it set.
+    pub no_gui: bool,
+    pub json: bool,
+}
+
+#[derive(specta::Type, serde::Serialize, serde::Deserialize, Debug)]
 pub struct LaunchOptions {
     pub mode: LaunchOptionsMode,
 }
@@ -20,38 +68,200 @@ pub fn get_launch_options() -> Result<LaunchOptions, AppError> {
 
     debug!("Command line arguments: {:?}", args);
 
-    parse_launch_options(args.collect())
+    parse_launch_options(args.collect()).and_then(with_stdin_image)
 }
 
 pub fn parse_launch_options(args: Vec<String>) -> Result<LaunchOptions, AppError> {
-    match (args.get(1), args.get(2)) {
-        (None, None) => Ok(LaunchOptions {
-            mode: LaunchOptionsMode::Default,
-        }),
-        (Some(args_1), args_2) if args_1 == "send" => {
-            let Some(file) = args_2 else {
+    let args = args.get(1..).unwrap_or_default();
+
+    // NOTE: Help and version win over everything else, like most command line tools.
+    for arg in args.iter().take_while(|arg| *arg != "--") {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                return Ok(LaunchOptions {
+                    mode: LaunchOptionsMode::Help,
+                })
+            }
+            "-V" | "--version" => {
+                return Ok(LaunchOptions {
+                    mode: LaunchOptionsMode::Version,
+                })
+            }
+            _ => {}
+        }
+    }
+
+    let mode = match args.first().map(|arg| arg.as_str()) {
+        None => LaunchOptionsMode::Default,
+        Some("capture") => {
+            if args.len() > 1 {
                 return Err(AppError::Unknown(
-                    "Send subcommand requires a file argument".to_string(),
+                    "Capture subcommand does not take arguments".to_string(),
                 ));
-            };
-            Ok(LaunchOptions {
-                mode: LaunchOptionsMode::Send { file: file.clone() },
-            })
+            }
+            LaunchOptionsMode::Capture
         }
-        (Some(args_1), args_2) if args_1 == "capture" => {
-            if args_2.is_some() {
+        Some(link) if crate::deep_link::is_deep_link(link) => {
+            if args.len() > 1 {
                 return Err(AppError::Unknown(
-                    "Capture subcommand does not take arguments".to_string(),
+                    "Deep link must be the only argument".to_string(),
                 ));
             }
-            Ok(LaunchOptions {
-                mode: LaunchOptionsMode::Capture,
-            })
+            crate::deep_link::parse_deep_link(link)?
         }
-        (Some(file), None) => Ok(LaunchOptions {
-            mode: LaunchOptionsMode::Send { file: file.clone() },
-        }),
-        _ => Err(AppError::Unknown("Invalid launch arguments".to_string())),
+        Some("send") => parse_send_args(&args[1..])?,
+        // Explorer's "Send to" and file associations pass files without a subcommand
+        Some(_) => parse_send_args(args)?,
+    };
+
+    Ok(LaunchOptions { mode })
+}
+
+fn parse_send_args(args: &[String]) -> Result<LaunchOptionsMode, AppError> {
+    let mut files = Vec::new();
+    let mut options = SendOptions::default();
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--mode" => {
+                options.mode = Some(parse_send_mode(option_value(&mut args, arg)?)?);
+            }
+            "--copy" => options.copy = Some(true),
+            "--no-copy" => options.copy = Some(false),
+            "--profile" => {
+                options.profile = Some(parse_profile(option_value(&mut args, arg)?)?);
+            }
+            "--no-gui" => options.no_gui = true,
+            "--json" => options.json = true,
+            "--" => files.extend(args.by_ref().cloned()),
+            option if option.starts_with('-') && option.len() > 1 => {
+                return Err(AppError::Unknown(format!("Unknown option: {}", option)));
+            }
+            _ => files.push(arg.clone()),
+        }
+    }
+
+    if files.is_empty() {
+        return Err(AppError::Unknown(
+            "Send subcommand requires a file argument".to_string(),
+        ));
+    }
+
+    if options.no_gui && options.mode.is_none() {
+        return Err(AppError::Unknown("--no-gui requires --mode".to_string()));
+    }
+
+    if options.json && !options.no_gui {
+        return Err(AppError::Unknown("--json requires --no-gui".to_string()));
+    }
+
+    if options.no_gui && options.copy.is_some() {
+        return Err(AppError::Unknown(
+            "--copy and --no-copy are not supported with --no-gui".to_string(),
+        ));
+    }
+
+    Ok(LaunchOptionsMode::Send { files, options })
+}
+
+/// Returns the profile the app should run with.
+pub fn profile_of(launch_options: &LaunchOptions) -> Option<String> {
+    match &launch_options.mode {
+        LaunchOptionsMode::Send { options, .. } => options.profile.clone(),
+        _ => None,
+    }
+}
+
+/// Replaces `-` with the path of the image read from standard input into a job workspace.
+pub fn with_stdin_image(launch_options: LaunchOptions) -> Result<LaunchOptions, AppError> {
+    let LaunchOptionsMode::Send { files, options } = launch_options.mode else {
+        return Ok(launch_options);
+    };
+
+    if !files.iter().any(|file| file == STDIN_FILE) {
+        return Ok(LaunchOptions {
+            mode: LaunchOptionsMode::Send { files, options },
+        });
+    }
+
+    let stdin_image = STDIN_IMAGE
+        .get_or_init(|| read_image_from(std::io::stdin().lock()))
+        .clone()?;
+
+    let files = files
+        .into_iter()
+        .map(|file| {
+            if file == STDIN_FILE {
+                stdin_image.clone()
+            } else {
+                file
+            }
+        })
+        .collect();
+
+    Ok(LaunchOptions {
+        mode: LaunchOptionsMode::Send { files, options },
+    })
+}
+
+fn read_image_from(reader: impl std::io::Read) -> Result<String, AppError> {
+    let mut bytes = Vec::new();
+
+    reader
+        .take(MAX_STDIN_BYTES + 1)
+        .read_to_end(&mut bytes)
+        .map_err(AppError::from_error_with_message(
+            "Failed to read image from standard input",
+        ))?;
+
+    if bytes.len() as u64 > MAX_STDIN_BYTES {
+        return Err(AppError::Unknown(format!(
+            "Image from standard input is larger than {} MB",
+            MAX_STDIN_BYTES / 1024 / 1024
+        )));
+    }
+
+    crate::jobs::save_image_bytes_to_workspace(&bytes)
+        .map(|path| path.to_string_lossy().into_owned())
+}
+
+fn option_value<'a>(
+    args: &mut impl Iterator<Item = &'a String>,
+    option: &str,
+) -> Result<&'a str, AppError> {
+    args.next()
+        .map(|value| value.as_str())
+        .ok_or(AppError::Unknown(format!("{} requires a value", option)))
+}
+
+// NOTE: The profile becomes part of a file name and a socket name.
+fn parse_profile(value: &str) -> Result<String, AppError> {
+    let is_valid = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+    if !is_valid {
+        return Err(AppError::Unknown(format!(
+            "Invalid profile name: {} (use letters, digits, - and _)",
+            value
+        )));
+    }
+
+    Ok(value.to_string())
+}
+
+pub fn parse_send_mode(value: &str) -> Result<crate::SendRequestEventMode, AppError> {
+    match value {
+        "video" => Ok(crate::SendRequestEventMode::UploadImageToVideoServer),
+        "image" => Ok(crate::SendRequestEventMode::UploadImageToImageServer),
+        "print" => Ok(crate::SendRequestEventMode::UploadImageToVRChatPrint),
+        _ => Err(AppError::Unknown(format!(
+            "Unknown send mode: {} (expected video, image or print)",
+            value
+        ))),
     }
 }
 
@@ -59,6 +269,13 @@ pub fn parse_launch_options(args: Vec<String>) -> Result<LaunchOptions, AppError
 mod tests {
     use super::*;
 
+    fn send_mode(files: &[&str]) -> LaunchOptionsMode {
+        LaunchOptionsMode::Send {
+            files: files.iter().map(|file| file.to_string()).collect(),
+            options: SendOptions::default(),
+        }
+    }
+
     #[test]
     fn parses_no_args() {
         let result = parse_launch_options(vec!["app".to_string()]).unwrap();
@@ -70,12 +287,7 @@ mod tests {
     fn parses_no_subcommand_with_file() {
         let result = parse_launch_options(vec!["app".to_string(), "file.txt".to_string()]).unwrap();
 
-        assert_eq!(
-            result.mode,
-            LaunchOptionsMode::Send {
-                file: "file.txt".to_string()
-            }
-        );
+        assert_eq!(result.mode, send_mode(&["file.txt"]));
     }
 
     #[test]
@@ -87,18 +299,185 @@ mod tests {
         ])
         .unwrap();
 
+        assert_eq!(result.mode, send_mode(&["file.txt"]));
+    }
+
+    #[test]
+    fn parses_capture_subcommand() {
+        let result = parse_launch_options(vec!["app".to_string(), "capture".to_string()]).unwrap();
+
+        assert_eq!(result.mode, LaunchOptionsMode::Capture);
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        std::iter::once("app")
+            .chain(args.iter().copied())
+            .map(|arg| arg.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn parses_multiple_files() {
+        let result = parse_launch_options(args(&["send", "a.png", "b.png"])).unwrap();
+        assert_eq!(result.mode, send_mode(&["a.png", "b.png"]));
+
+        let result = parse_launch_options(args(&["a.png", "b.png"])).unwrap();
+        assert_eq!(result.mode, send_mode(&["a.png", "b.png"]));
+    }
+
+    #[test]
+    fn parses_send_options() {
+        let result = parse_launch_options(args(&[
+            "send",
+            "--mode",
+            "image",
+            "a.png",
+            "--no-copy",
+            "--profile",
+            "work",
+        ]))
+        .unwrap();
+
         assert_eq!(
             result.mode,
             LaunchOptionsMode::Send {
-                file: "file.txt".to_string()
+                files: vec!["a.png".to_string()],
+                options: SendOptions {
+                    mode: Some(crate::SendRequestEventMode::UploadImageToImageServer),
+                    copy: Some(false),
+                    profile: Some("work".to_string()),
+                    ..Default::default()
+                },
             }
         );
     }
 
     #[test]
-    fn parses_capture_subcommand() {
-        let result = parse_launch_options(vec!["app".to_string(), "capture".to_string()]).unwrap();
+    fn parses_files_after_separator() {
+        let result = parse_launch_options(args(&["send", "--copy", "--", "--help.png"])).unwrap();
 
+        assert_eq!(
+            result.mode,
+            LaunchOptionsMode::Send {
+                files: vec!["--help.png".to_string()],
+                options: SendOptions {
+                    copy: Some(true),
+                    ..Default::default()
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn parses_help_and_version() {
+        assert_eq!(
+            parse_launch_options(args(&["--help"])).unwrap().mode,
+            LaunchOptionsMode::Help
+        );
+        assert_eq!(
+            parse_launch_options(args(&["send", "a.png", "-h"]))
+                .unwrap()
+                .mode,
+            LaunchOptionsMode::Help
+        );
+        assert_eq!(
+            parse_launch_options(args(&["-V"])).unwrap().mode,
+            LaunchOptionsMode::Version
+        );
+    }
+
+    #[test]
+    fn parses_deep_link() {
+        let result = parse_launch_options(args(&["send-to-vrc://capture"])).unwrap();
         assert_eq!(result.mode, LaunchOptionsMode::Capture);
+
+        assert!(parse_launch_options(args(&["send-to-vrc://capture", "a.png"])).is_err());
+    }
+
+    #[test]
+    fn parses_headless_send() {
+        let result =
+            parse_launch_options(args(&["send", "--mode", "print", "--no-gui", "file.png"]))
+                .unwrap();
+
+        assert_eq!(
+            result.mode,
+            LaunchOptionsMode::Send {
+                files: vec!["file.png".to_string()],
+                options: SendOptions {
+                    mode: Some(crate::SendRequestEventMode::UploadImageToVRChatPrint),
+                    no_gui: true,
+                    ..Default::default()
+                },
+            }
+        );
+
+        let result = parse_launch_options(args(&[
+            "send", "--no-gui", "--json", "file.png", "--mode", "video",
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            result.mode,
+            LaunchOptionsMode::Send {
+                files: vec!["file.png".to_string()],
+                options: SendOptions {
+                    mode: Some(crate::SendRequestEventMode::UploadImageToVideoServer),
+                    no_gui: true,
+                    json: true,
+                    ..Default::default()
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_send_options() {
+        assert!(parse_launch_options(args(&["send"])).is_err());
+        assert!(parse_launch_options(args(&["send", "--no-gui", "file.png"])).is_err());
+        assert!(parse_launch_options(args(&["send", "--json", "file.png"])).is_err());
+        assert!(
+            parse_launch_options(args(&["send", "--no-gui", "--mode", "gif", "a.png"])).is_err()
+        );
+        assert!(parse_launch_options(args(&["send", "--no-gui", "--mode"])).is_err());
+        assert!(parse_launch_options(args(&["send", "--verbose", "file.png"])).is_err());
+        assert!(parse_launch_options(args(&["capture", "file.png"])).is_err());
+        assert!(parse_launch_options(args(&["send", "--profile", "../work", "a.png"])).is_err());
+        assert!(parse_launch_options(args(&["send", "--profile", "", "a.png"])).is_err());
+        assert!(parse_launch_options(args(&[
+            "send", "--no-gui", "--mode", "video", "--copy", "a.png"
+        ]))
+        .is_err());
+    }
+
+    #[test]
+    fn reads_profile_of_send() {
+        let launch_options =
+            parse_launch_options(args(&["send", "--profile", "work", "a.png"])).unwrap();
+        assert_eq!(profile_of(&launch_options), Some("work".to_string()));
+
+        let launch_options = parse_launch_options(args(&["capture"])).unwrap();
+        assert_eq!(profile_of(&launch_options), None);
+    }
+
+    #[test]
+    fn keeps_files_without_stdin() {
+        let launch_options = parse_launch_options(args(&["send", "a.png"])).unwrap();
+
+        assert_eq!(
+            with_stdin_image(launch_options).unwrap().mode,
+            send_mode(&["a.png"])
+        );
+    }
+
+    #[test]
+    fn reads_image_from_stdin() {
+        let input_file =
+            std::env::var("CARGO_MANIFEST_DIR").unwrap() + "/test_data/input_image.png";
+
+        let path = read_image_from(std::fs::File::open(input_file).unwrap()).unwrap();
+
+        assert!(image::open(path).is_ok());
+        assert!(read_image_from(&b"not an image"[..]).is_err());
     }
 }
@@ -4,18 +4,34 @@ use tauri_plugin_opener::OpenerExt;
 use crate::prelude::*;
 mod app_data;
 mod capture;
+mod clipboard;
+mod clipboard_watcher;
 mod config;
+mod control_api;
 mod crypt;
+mod deep_link;
 mod error;
 mod file;
+mod headless;
+mod hotkeys;
 mod image;
 mod image_to_image;
 mod image_to_video;
+mod jobs;
 mod launch_options;
+mod osc;
+mod osc_server;
+mod oscquery;
 mod prelude;
+mod preview;
 mod progress;
+mod remote_image;
+mod screenshot_watcher;
+mod send;
+mod single_instance;
 mod uploader;
 mod vrchat_print;
+mod vrchat_queue;
 
 fn generate_binding_file(builder: &tauri_specta::Builder<tauri::Wry>) {
     let binding_str = builder
@@ -33,6 +49,22 @@ fn generate_binding_file(builder: &tauri_specta::Builder<tauri::Wry>) {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    let args: Vec<String> = std::env::args().collect();
+
+    if let Some(exit_code) = headless::run_command_line(&args) {
+        std::process::exit(exit_code);
+    }
+
+    if let Ok(launch_options) =
+        launch_options::parse_launch_options(args).and_then(launch_options::with_stdin_image)
+    {
+        config::set_profile(launch_options::profile_of(&launch_options));
+
+        if single_instance::forward_to_running_instance(launch_options) {
+            return;
+        }
+    }
+
     let builder = tauri_specta::Builder::<tauri::Wry>::new()
         // Then register them (separated by a comma)
         .commands(tauri_specta::collect_commands![
@@ -43,9 +75,12 @@ pub fn run() {
             get_system_locale,
             kill,
             image::is_able_to_read_image_file,
+            image::read_screenshot_metadata,
             image_to_video::upload_image_to_video_server,
             image_to_image::upload_image_to_image_server,
             vrchat_print::upload_image_to_vrchat_print,
+            vrchat_print::upload_images_to_vrchat_print,
+            preview::render_send_preview,
             vrchat_print::login_to_vrchat,
             vrchat_print::login_to_vrchat_get_current_user_name,
             vrchat_print::login_to_vrchat_submit_totp_code,
@@ -54,14 +89,39 @@ pub fn run() {
             uploader::register_anonymously,
             uploader::get_tos_and_version,
             capture::start_capture,
+            capture::start_delayed_capture,
             capture::stop_capture,
+            capture::get_capture_state,
             capture::finish_capture_with_cropped_rect,
+            capture::finish_capture_with_desktop_rect,
             capture::get_capture_url_command,
+            capture::repeat_last_capture,
+            capture::window::list_capture_windows,
+            capture::window::capture_window,
+            capture::window::capture_vrchat_window,
+            capture::live_board::start_live_board,
+            capture::live_board::stop_live_board,
             config::config_file_path,
             config::reset_config,
             launch_options::get_launch_options,
+            screenshot_watcher::default_screenshot_directory,
+            osc_server::restart_osc_listener,
+            control_api::restart_control_api,
+            hotkeys::register_hotkeys,
+            send::send_clipboard_image,
         ])
-        .events(tauri_specta::collect_events![SendRequestEvent,]);
+        .events(tauri_specta::collect_events![
+            SendRequestEvent,
+            vrchat_queue::VrchatQueueEvent,
+            vrchat_print::BatchPrintProgressEvent,
+            send::SendResultEvent,
+            screenshot_watcher::ScreenshotDetectedEvent,
+            clipboard_watcher::ClipboardImageDetectedEvent,
+            capture::CaptureCountdownEvent,
+            capture::CaptureStateEvent,
+            capture::CaptureFailedEvent,
+            capture::live_board::LiveBoardStatusEvent,
+        ]);
 
     #[cfg(debug_assertions)] // <- Only export on non-release builds
     generate_binding_file(&builder);
@@ -72,14 +132,40 @@ pub fn run() {
         .plugin(tauri_plugin_log::Builder::new().build())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_opener::init())
-        .invoke_handler(builder.invoke_handler())
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(hotkeys::plugin())
+        .invoke_handler({
+            let specta_handler = builder.invoke_handler();
+            // NOTE: Commands taking a raw body cannot be described by tauri-specta.
+            let raw_body_handler =
+                tauri::generate_handler![send::send_image_bytes, clipboard::write_clipboard_image];
+
+            move |invoke| match invoke.message.command() {
+                "send_image_bytes" | "write_clipboard_image" => raw_body_handler(invoke),
+                _ => specta_handler(invoke),
+            }
+        })
         .setup(move |app| {
             app.manage(app_data::AppData::new(app.handle().clone()));
 
             builder.mount_events(&app.handle().clone());
 
+            single_instance::init_instance_server(app.handle().clone());
+
+            deep_link::init_deep_link(app.handle().clone());
+
             capture::init_capture_thread(app.handle().clone());
 
+            screenshot_watcher::init_screenshot_watcher(app.handle().clone());
+
+            clipboard_watcher::init_clipboard_watcher(app.handle().clone());
+
+            hotkeys::init_hotkeys(app.handle().clone());
+
+            osc_server::init_osc_listener(app.handle().clone());
+
+            control_api::init_control_api(app.handle().clone());
+
             Ok(())
         })
         .run(tauri::generate_context!())
@@ -137,7 +223,7 @@ fn open_resource_dir(handle: tauri::AppHandle) -> Result<(), AppError> {
     Ok(())
 }
 
-#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, specta::Type)]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq, specta::Type)]
 pub enum SendRequestEventMode {
     UploadImageToVideoServer,
     UploadImageToImageServer,
@@ -148,6 +234,15 @@ pub enum SendRequestEventMode {
 pub struct SendRequestEvent {
     file: String,
     mode: Option<SendRequestEventMode>,
+    /// Highlighted instead of sent right away, see `SendOptions::preselected_mode`.
+    #[serde(default)]
+    preselected_mode: Option<SendRequestEventMode>,
+    /// Overrides `should_copy_after_upload` when set.
+    #[serde(default)]
+    copy: Option<bool>,
+    /// Copies kept by capture auto-save.
+    #[serde(default)]
+    saved_files: Vec<String>,
 }
 
 #[tauri::command]
@@ -225,6 +320,7 @@ mod test {
             &input_file,
             &test_api_key(),
             &test_uploader_url_base_url(),
+            &crate::jobs::create_workspace().unwrap(),
             None,
         )
         .await;
@@ -243,6 +339,7 @@ mod test {
             &input_file,
             &test_api_key(),
             &test_uploader_url_base_url(),
+            &crate::jobs::create_workspace().unwrap(),
             None,
         )
         .await;
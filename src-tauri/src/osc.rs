@@ -0,0 +1,369 @@
+use crate::prelude::*;
+
+const DEFAULT_CHATBOX_PORT: u16 = 9000;
+const DEFAULT_CHATBOX_TEMPLATE: &str = "{url}";
+// NOTE: VRChat truncates chatbox messages longer than this.
+const CHATBOX_MAX_CHARS: usize = 144;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OscArgument {
+    Int(i32),
+    Float(f32),
+    String(String),
+    Bool(bool),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OscMessage {
+    pub address: String,
+    pub arguments: Vec<OscArgument>,
+}
+
+impl OscMessage {
+    pub fn new(address: &str, arguments: Vec<OscArgument>) -> Self {
+        Self {
+            address: address.to_string(),
+            arguments,
+        }
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut packet = Vec::new();
+        write_padded_string(&mut packet, &self.address);
+
+        let type_tags: String = std::iter::once(',')
+            .chain(self.arguments.iter().map(|argument| match argument {
+                OscArgument::Int(_) => 'i',
+                OscArgument::Float(_) => 'f',
+                OscArgument::String(_) => 's',
+                OscArgument::Bool(true) => 'T',
+                OscArgument::Bool(false) => 'F',
+            }))
+            .collect();
+        write_padded_string(&mut packet, &type_tags);
+
+        for argument in &self.arguments {
+            match argument {
+                OscArgument::Int(value) => packet.extend_from_slice(&value.to_be_bytes()),
+                OscArgument::Float(value) => packet.extend_from_slice(&value.to_be_bytes()),
+                OscArgument::String(value) => write_padded_string(&mut packet, value),
+                OscArgument::Bool(_) => {}
+            }
+        }
+
+        packet
+    }
+
+    pub fn decode(packet: &[u8]) -> Result<Self, AppError> {
+        let (address, rest) = read_padded_string(packet)?;
+
+        if !address.starts_with('/') {
+            return Err(AppError::Unknown(format!(
+                "Invalid OSC address: {}",
+                address
+            )));
+        }
+
+        // NOTE: Type tags are optional in OSC 1.0
+        if rest.is_empty() {
+            return Ok(Self::new(&address, Vec::new()));
+        }
+
+        let (type_tags, mut rest) = read_padded_string(rest)?;
+
+        let Some(type_tags) = type_tags.strip_prefix(',') else {
+            return Err(AppError::Unknown("Invalid OSC type tags".to_string()));
+        };
+
+        let mut arguments = Vec::new();
+
+        for type_tag in type_tags.chars() {
+            let argument = match type_tag {
+                'i' => {
+                    let (bytes, next) = read_4_bytes(rest)?;
+                    rest = next;
+                    OscArgument::Int(i32::from_be_bytes(bytes))
+                }
+                'f' => {
+                    let (bytes, next) = read_4_bytes(rest)?;
+                    rest = next;
+                    OscArgument::Float(f32::from_be_bytes(bytes))
+                }
+                's' => {
+                    let (value, next) = read_padded_string(rest)?;
+                    rest = next;
+                    OscArgument::String(value)
+                }
+                'T' => OscArgument::Bool(true),
+                'F' => OscArgument::Bool(false),
+                _ => {
+                    return Err(AppError::Unknown(format!(
+                        "Unsupported OSC type tag: {}",
+                        type_tag
+                    )))
+                }
+            };
+
+            arguments.push(argument);
+        }
+
+        Ok(Self::new(&address, arguments))
+    }
+}
+
+/// Decodes a packet that is either a single message or a (possibly nested) bundle.
+pub fn decode_packet(packet: &[u8]) -> Result<Vec<OscMessage>, AppError> {
+    let Some(mut rest) = packet.strip_prefix(b"#bundle\0") else {
+        return Ok(vec![OscMessage::decode(packet)?]);
+    };
+
+    // time tag is ignored, everything is handled immediately
+    rest = rest
+        .get(8..)
+        .ok_or(AppError::Unknown("Truncated OSC bundle".to_string()))?;
+
+    let mut messages = Vec::new();
+
+    while !rest.is_empty() {
+        let (size, next) = read_4_bytes(rest)?;
+        let size = i32::from_be_bytes(size) as usize;
+
+        let element = next.get(..size).ok_or(AppError::Unknown(
+            "Truncated OSC bundle element".to_string(),
+        ))?;
+
+        messages.extend(decode_packet(element)?);
+        rest = &next[size..];
+    }
+
+    Ok(messages)
+}
+
+fn write_padded_string(packet: &mut Vec<u8>, value: &str) {
+    packet.extend_from_slice(value.as_bytes());
+    // null terminated and padded to 4 bytes
+    packet.extend(std::iter::repeat_n(0, 4 - value.len() % 4));
+}
+
+fn read_padded_string(packet: &[u8]) -> Result<(String, &[u8]), AppError> {
+    let length = packet
+        .iter()
+        .position(|&c| c == 0)
+        .ok_or(AppError::Unknown("Unterminated OSC string".to_string()))?;
+
+    let value = std::str::from_utf8(&packet[..length])
+        .map_err(AppError::from_error_with_message("Invalid OSC string"))?
+        .to_string();
+
+    let padded_length = (length / 4 + 1) * 4;
+
+    Ok((value, packet.get(padded_length..).unwrap_or_default()))
+}
+
+fn read_4_bytes(packet: &[u8]) -> Result<([u8; 4], &[u8]), AppError> {
+    let bytes = packet
+        .get(..4)
+        .ok_or(AppError::Unknown("Truncated OSC argument".to_string()))?;
+
+    Ok(([bytes[0], bytes[1], bytes[2], bytes[3]], &packet[4..]))
+}
+
+pub fn send_osc_message(
+    target: std::net::SocketAddr,
+    message: &OscMessage,
+) -> Result<(), AppError> {
+    let socket = std::net::UdpSocket::bind(("127.0.0.1", 0)).map_err(
+        AppError::from_error_with_message("Failed to bind OSC socket"),
+    )?;
+
+    socket
+        .send_to(&message.encode(), target)
+        .map_err(AppError::from_error_with_message(
+            "Failed to send OSC message",
+        ))?;
+
+    Ok(())
+}
+
+/// Renders `template` with the first `{url}` replaced by `url`. Only the text around the URL is
+/// cut to fit the chatbox, since a cut URL would be a broken link.
+pub fn chatbox_message(template: &str, url: &str) -> Result<OscMessage, AppError> {
+    let text: String = match template.split_once("{url}") {
+        Some((before, after)) => {
+            let url_length = url.chars().count();
+
+            if url_length > CHATBOX_MAX_CHARS {
+                return Err(AppError::Unknown(format!(
+                    "URL is too long for the chatbox ({} characters)",
+                    url_length
+                )));
+            }
+
+            let before: String = before
+                .chars()
+                .take(CHATBOX_MAX_CHARS - url_length)
+                .collect();
+            let after: String = after
+                .replace("{url}", "")
+                .chars()
+                .take(CHATBOX_MAX_CHARS - url_length - before.chars().count())
+                .collect();
+
+            format!("{}{}{}", before, url, after)
+        }
+        None => template.chars().take(CHATBOX_MAX_CHARS).collect(),
+    };
+
+    // text, send immediately (skip the keyboard), play notification sound
+    Ok(OscMessage::new(
+        "/chatbox/input",
+        vec![
+            OscArgument::String(text),
+            OscArgument::Bool(true),
+            OscArgument::Bool(true),
+        ],
+    ))
+}
+
+/// Posts the uploaded URL to the VRChat chatbox when `osc_chatbox_enabled` is set. Failures are only logged.
+pub fn post_url_to_chatbox(config: &crate::config::Config, url: &str) {
+    if !config.get_bool("osc_chatbox_enabled", false) {
+        return;
+    }
+
+    let port = config
+        .get("osc_chatbox_port")
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(DEFAULT_CHATBOX_PORT);
+
+    let template = config
+        .get("osc_chatbox_template")
+        .filter(|template| !template.is_empty())
+        .unwrap_or(DEFAULT_CHATBOX_TEMPLATE);
+
+    let target = std::net::SocketAddr::from(([127, 0, 0, 1], port));
+
+    match chatbox_message(template, url).and_then(|message| send_osc_message(target, &message)) {
+        Ok(_) => info!("Posted URL to VRChat chatbox on port {}", port),
+        Err(e) => warn!("Failed to post URL to VRChat chatbox: {:?}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_message() {
+        let message = OscMessage::new(
+            "/chatbox/input",
+            vec![
+                OscArgument::String("hi".to_string()),
+                OscArgument::Bool(true),
+            ],
+        );
+
+        assert_eq!(message.encode(), b"/chatbox/input\0\0,sT\0hi\0\0".to_vec());
+    }
+
+    #[test]
+    fn decodes_encoded_message() {
+        let message = OscMessage::new(
+            "/avatar/parameters/S2V_Capture",
+            vec![
+                OscArgument::Int(-3),
+                OscArgument::Float(0.5),
+                OscArgument::String("test".to_string()),
+                OscArgument::Bool(false),
+            ],
+        );
+
+        assert_eq!(OscMessage::decode(&message.encode()).unwrap(), message);
+    }
+
+    #[test]
+    fn rejects_malformed_packets() {
+        assert!(OscMessage::decode(b"no_slash\0\0\0\0").is_err());
+        assert!(OscMessage::decode(b"/address").is_err());
+        assert!(OscMessage::decode(b"/a\0\0,i\0\0\0\0").is_err());
+    }
+
+    #[test]
+    fn decodes_bundles() {
+        let first = OscMessage::new("/a", vec![OscArgument::Bool(true)]);
+        let second = OscMessage::new("/b", vec![OscArgument::Int(1)]);
+
+        let mut bundle = b"#bundle\0".to_vec();
+        bundle.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 1]);
+        for message in [&first, &second] {
+            let encoded = message.encode();
+            bundle.extend_from_slice(&(encoded.len() as i32).to_be_bytes());
+            bundle.extend_from_slice(&encoded);
+        }
+
+        assert_eq!(decode_packet(&bundle).unwrap(), vec![first, second]);
+    }
+
+    #[test]
+    fn truncates_chatbox_message_around_url() {
+        let text = |template: &str, url: &str| {
+            let message = chatbox_message(template, url).unwrap();
+
+            let OscArgument::String(text) = &message.arguments[0] else {
+                panic!("Expected string argument");
+            };
+
+            text.clone()
+        };
+
+        let url = "https://example.com/".to_string() + &"a".repeat(100);
+
+        let long = text(
+            &format!("{}{{url}} {}", "b".repeat(30), "c".repeat(30)),
+            &url,
+        );
+        assert_eq!(long.chars().count(), CHATBOX_MAX_CHARS);
+        assert!(long.starts_with(&format!("{}{}", "b".repeat(24), url)));
+
+        let long = text(&format!("Now showing: {{url}} {}", "c".repeat(100)), &url);
+        assert_eq!(long.chars().count(), CHATBOX_MAX_CHARS);
+        assert!(long.starts_with(&format!("Now showing: {} c", url)));
+
+        assert_eq!(
+            text("Now showing: {url}!", "https://a.b/c"),
+            "Now showing: https://a.b/c!"
+        );
+
+        let too_long = "https://example.com/".to_string() + &"a".repeat(200);
+        assert!(chatbox_message("{url}", &too_long).is_err());
+    }
+
+    #[test]
+    fn sends_chatbox_message_over_udp() {
+        let receiver = std::net::UdpSocket::bind(("127.0.0.1", 0)).unwrap();
+        receiver
+            .set_read_timeout(Some(std::time::Duration::from_secs(5)))
+            .unwrap();
+
+        send_osc_message(
+            receiver.local_addr().unwrap(),
+            &chatbox_message("{url}", "https://example.com/video.mp4").unwrap(),
+        )
+        .unwrap();
+
+        let mut buffer = [0u8; 1024];
+        let (length, _) = receiver.recv_from(&mut buffer).unwrap();
+
+        assert_eq!(
+            OscMessage::decode(&buffer[..length]).unwrap(),
+            OscMessage::new(
+                "/chatbox/input",
+                vec![
+                    OscArgument::String("https://example.com/video.mp4".to_string()),
+                    OscArgument::Bool(true),
+                    OscArgument::Bool(true),
+                ]
+            )
+        );
+    }
+}
@@ -0,0 +1,394 @@
+use crate::osc::{OscArgument, OscMessage};
+use crate::prelude::*;
+use tauri::Manager as _;
+
+const DEFAULT_BINDINGS: &str = "/avatar/parameters/S2V_Capture=StartCapture,/avatar/parameters/S2V_ResendVideo=ResendLastFileToVideoServer,/avatar/parameters/S2V_PrintLast=PrintLastScreenshot";
+const RECEIVE_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(500);
+
+static OSC_LISTENER: std::sync::Mutex<Option<OscListenerThread>> = std::sync::Mutex::new(None);
+
+struct OscListenerThread {
+    stop: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    thread: std::thread::JoinHandle<()>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OscAction {
+    StartCapture,
+    ResendLastFileToVideoServer,
+    PrintLastScreenshot,
+}
+
+impl std::str::FromStr for OscAction {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "StartCapture" => Ok(OscAction::StartCapture),
+            "ResendLastFileToVideoServer" => Ok(OscAction::ResendLastFileToVideoServer),
+            "PrintLastScreenshot" => Ok(OscAction::PrintLastScreenshot),
+            _ => Err(AppError::ConfigContents(format!(
+                "Unknown OSC action: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Parses `address=Action` pairs separated by commas.
+pub fn parse_bindings(bindings: &str) -> Result<Vec<(String, OscAction)>, AppError> {
+    bindings
+        .split(',')
+        .map(|binding| binding.trim())
+        .filter(|binding| !binding.is_empty())
+        .map(|binding| {
+            let Some((address, action)) = binding.split_once('=') else {
+                return Err(AppError::ConfigContents(format!(
+                    "Invalid OSC binding: {}",
+                    binding
+                )));
+            };
+
+            let address = address.trim();
+
+            if !address.starts_with('/') {
+                return Err(AppError::ConfigContents(format!(
+                    "Invalid OSC address: {}",
+                    address
+                )));
+            }
+
+            Ok((address.to_string(), action.trim().parse()?))
+        })
+        .collect()
+}
+
+pub struct OscListener {
+    socket: std::net::UdpSocket,
+    bindings: Vec<(String, OscAction)>,
+    // Actions fire on rising edges only, because VRChat resends parameters on avatar change.
+    active: std::collections::HashMap<String, bool>,
+}
+
+impl OscListener {
+    pub fn bind(port: u16, bindings: Vec<(String, OscAction)>) -> Result<Self, AppError> {
+        let socket = std::net::UdpSocket::bind(("127.0.0.1", port)).map_err(
+            AppError::from_error_with_message("Failed to bind OSC listener socket"),
+        )?;
+
+        socket.set_read_timeout(Some(RECEIVE_TIMEOUT)).map_err(
+            AppError::from_error_with_message("Failed to set OSC listener timeout"),
+        )?;
+
+        Ok(Self {
+            socket,
+            bindings,
+            active: std::collections::HashMap::new(),
+        })
+    }
+
+    pub fn port(&self) -> Result<u16, AppError> {
+        Ok(self
+            .socket
+            .local_addr()
+            .map_err(AppError::from_error_with_message(
+                "Failed to get OSC listener address",
+            ))?
+            .port())
+    }
+
+    pub fn addresses(&self) -> Vec<String> {
+        self.bindings
+            .iter()
+            .map(|(address, _)| address.clone())
+            .collect()
+    }
+
+    /// Waits for one packet and returns the actions it triggered. Returns nothing on timeout.
+    pub fn receive_actions(&mut self) -> Result<Vec<OscAction>, AppError> {
+        let mut buffer = [0u8; 4096];
+
+        let length = match self.socket.recv(&mut buffer) {
+            Ok(length) => length,
+            Err(e)
+                if e.kind() == std::io::ErrorKind::WouldBlock
+                    || e.kind() == std::io::ErrorKind::TimedOut =>
+            {
+                return Ok(Vec::new())
+            }
+            Err(e) => {
+                return Err(AppError::from_error_with_message(
+                    "Failed to receive OSC packet",
+                )(e))
+            }
+        };
+
+        let messages = match crate::osc::decode_packet(&buffer[..length]) {
+            Ok(messages) => messages,
+            Err(e) => {
+                debug!("Ignoring malformed OSC packet: {:?}", e);
+                return Ok(Vec::new());
+            }
+        };
+
+        Ok(messages
+            .iter()
+            .filter_map(|message| self.handle_message(message))
+            .collect())
+    }
+
+    fn handle_message(&mut self, message: &OscMessage) -> Option<OscAction> {
+        let (address, action) = self
+            .bindings
+            .iter()
+            .find(|(address, _)| *address == message.address)?;
+
+        let is_active = message.arguments.first().is_some_and(is_truthy);
+        let was_active = self.active.insert(address.clone(), is_active) == Some(true);
+
+        (is_active && !was_active).then_some(*action)
+    }
+}
+
+fn is_truthy(argument: &OscArgument) -> bool {
+    match argument {
+        OscArgument::Bool(value) => *value,
+        OscArgument::Int(value) => *value != 0,
+        OscArgument::Float(value) => *value >= 0.5,
+        OscArgument::String(_) => false,
+    }
+}
+
+pub fn init_osc_listener(app_handle: tauri::AppHandle) {
+    // NOTE: Hold the lock until the new thread is stored so concurrent restarts cannot start two.
+    let mut current = OSC_LISTENER.lock().unwrap();
+
+    // NOTE: Wait for the previous listener to release the port before binding it again.
+    if let Some(previous) = current.take() {
+        previous
+            .stop
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+        previous
+            .thread
+            .join()
+            .unwrap_or_else(|_| error!("OSC listener thread panicked"));
+    }
+
+    let settings = {
+        let state = app_handle.state::<crate::app_data::AppData>();
+
+        if !state.is_healthy() {
+            return;
+        }
+
+        let config = state.lock_config();
+
+        if !config.get_bool("osc_listener_enabled", false) {
+            info!("OSC listener is disabled");
+            return;
+        }
+
+        let port = config
+            .get("osc_listener_port")
+            .and_then(|port| port.parse().ok())
+            .unwrap_or(0);
+
+        let bindings = config
+            .get("osc_listener_bindings")
+            .filter(|bindings| !bindings.trim().is_empty())
+            .unwrap_or(DEFAULT_BINDINGS)
+            .to_string();
+
+        (port, bindings)
+    };
+
+    let stop = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+    let thread = {
+        let stop = stop.clone();
+
+        std::thread::spawn(move || {
+            let (port, bindings) = settings;
+
+            match osc_listener_thread(&app_handle, &stop, port, &bindings) {
+                Ok(_) => info!("OSC listener exited normally"),
+                Err(e) => error!("OSC listener exited with error: {:?}", e),
+            }
+        })
+    };
+
+    *current = Some(OscListenerThread { stop, thread });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn restart_osc_listener(handle: tauri::AppHandle) {
+    // NOTE: Stopping waits for the previous thread to exit, which must not block the event loop.
+    tauri::async_runtime::spawn_blocking(move || init_osc_listener(handle))
+        .await
+        .unwrap_or_else(|e| error!("Failed to restart OSC listener: {:?}", e));
+}
+
+fn osc_listener_thread(
+    app_handle: &tauri::AppHandle,
+    stop: &std::sync::atomic::AtomicBool,
+    port: u16,
+    bindings: &str,
+) -> Result<(), AppError> {
+    let mut listener = OscListener::bind(port, parse_bindings(bindings)?)?;
+    let port = listener.port()?;
+
+    info!("OSC listener is listening on port {}", port);
+
+    let oscquery = crate::oscquery::OscQueryService::start(port, &listener.addresses())
+        .inspect_err(|e| warn!("Failed to start OSCQuery service: {:?}", e))
+        .ok();
+
+    while !stop.load(std::sync::atomic::Ordering::SeqCst) {
+        let actions = match listener.receive_actions() {
+            Ok(actions) => actions,
+            Err(e) => {
+                // NOTE: e.g. ICMP port unreachable on Windows, which does not break the socket
+                warn!("Failed to receive OSC packet: {:?}", e);
+                std::thread::sleep(RECEIVE_TIMEOUT);
+                continue;
+            }
+        };
+
+        for action in actions {
+            info!("OSC action triggered: {:?}", action);
+            run_action(app_handle, action);
+        }
+    }
+
+    if let Some(oscquery) = oscquery {
+        oscquery.stop();
+    }
+
+    Ok(())
+}
+
+fn run_action(app_handle: &tauri::AppHandle, action: OscAction) {
+    let (file, mode) = match action {
+        OscAction::StartCapture => {
+            if let Err(e) = crate::capture::start_capture() {
+                error!("Failed to start capture from OSC: {}", e);
+            }
+            return;
+        }
+        OscAction::ResendLastFileToVideoServer => (
+            crate::send::last_sent_file(),
+            crate::SendRequestEventMode::UploadImageToVideoServer,
+        ),
+        OscAction::PrintLastScreenshot => (
+            crate::screenshot_watcher::latest_screenshot(app_handle)
+                .map(|path| path.to_string_lossy().into_owned()),
+            crate::SendRequestEventMode::UploadImageToVRChatPrint,
+        ),
+    };
+
+    let Some(file) = file else {
+        warn!("No file to send for OSC action {:?}", action);
+        return;
+    };
+
+    crate::send::spawn_send_job(app_handle, file, mode);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn send_to(listener: &OscListener, message: &OscMessage) {
+        let target = std::net::SocketAddr::from(([127, 0, 0, 1], listener.port().unwrap()));
+        crate::osc::send_osc_message(target, message).unwrap();
+    }
+
+    fn parameter(name: &str, argument: OscArgument) -> OscMessage {
+        OscMessage::new(&format!("/avatar/parameters/{}", name), vec![argument])
+    }
+
+    #[test]
+    fn parses_default_bindings() {
+        let bindings = parse_bindings(DEFAULT_BINDINGS).unwrap();
+
+        assert_eq!(
+            bindings[0],
+            (
+                "/avatar/parameters/S2V_Capture".to_string(),
+                OscAction::StartCapture
+            )
+        );
+        assert_eq!(bindings.len(), 3);
+    }
+
+    #[test]
+    fn rejects_invalid_bindings() {
+        assert!(parse_bindings("/avatar/parameters/A").is_err());
+        assert!(parse_bindings("avatar=StartCapture").is_err());
+        assert!(parse_bindings("/avatar/parameters/A=Explode").is_err());
+    }
+
+    #[test]
+    fn triggers_action_on_rising_edge() {
+        let mut listener = OscListener::bind(0, parse_bindings(DEFAULT_BINDINGS).unwrap()).unwrap();
+
+        send_to(
+            &listener,
+            &parameter("S2V_Capture", OscArgument::Bool(true)),
+        );
+        assert_eq!(
+            listener.receive_actions().unwrap(),
+            vec![OscAction::StartCapture]
+        );
+
+        // still held
+        send_to(
+            &listener,
+            &parameter("S2V_Capture", OscArgument::Bool(true)),
+        );
+        assert!(listener.receive_actions().unwrap().is_empty());
+
+        send_to(
+            &listener,
+            &parameter("S2V_Capture", OscArgument::Bool(false)),
+        );
+        assert!(listener.receive_actions().unwrap().is_empty());
+
+        send_to(&listener, &parameter("S2V_Capture", OscArgument::Int(1)));
+        assert_eq!(
+            listener.receive_actions().unwrap(),
+            vec![OscAction::StartCapture]
+        );
+    }
+
+    #[test]
+    fn ignores_unbound_and_malformed_packets() {
+        let mut listener = OscListener::bind(0, parse_bindings(DEFAULT_BINDINGS).unwrap()).unwrap();
+
+        send_to(&listener, &parameter("VelocityX", OscArgument::Float(1.0)));
+        assert!(listener.receive_actions().unwrap().is_empty());
+
+        let client = std::net::UdpSocket::bind(("127.0.0.1", 0)).unwrap();
+        client
+            .send_to(b"garbage", ("127.0.0.1", listener.port().unwrap()))
+            .unwrap();
+        assert!(listener.receive_actions().unwrap().is_empty());
+
+        send_to(
+            &listener,
+            &parameter("S2V_PrintLast", OscArgument::Float(1.0)),
+        );
+        assert_eq!(
+            listener.receive_actions().unwrap(),
+            vec![OscAction::PrintLastScreenshot]
+        );
+    }
+
+    #[test]
+    fn returns_nothing_on_timeout() {
+        let mut listener = OscListener::bind(0, Vec::new()).unwrap();
+
+        assert!(listener.receive_actions().unwrap().is_empty());
+    }
+}
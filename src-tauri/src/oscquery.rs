@@ -0,0 +1,279 @@
+use crate::prelude::*;
+
+const SERVICE_NAME: &str = "Send to VRC";
+
+// OSCQuery access values
+const ACCESS_NONE: u8 = 0;
+const ACCESS_WRITE_ONLY: u8 = 2;
+
+/// Advertises the OSC listener so VRChat sends avatar parameters to it without manual port configuration.
+pub struct OscQueryService {
+    stop: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    mdns: Option<mdns_sd::ServiceDaemon>,
+}
+
+impl OscQueryService {
+    pub fn start(osc_port: u16, addresses: &[String]) -> Result<Self, AppError> {
+        let server = tiny_http::Server::http("127.0.0.1:0")
+            .map_err(|e| AppError::Unknown(format!("Failed to start OSCQuery server: {}", e)))?;
+
+        let http_port = server
+            .server_addr()
+            .to_ip()
+            .ok_or(AppError::Unknown(
+                "OSCQuery server is not bound to an IP address".to_string(),
+            ))?
+            .port();
+
+        info!("OSCQuery server is listening on port {}", http_port);
+
+        let stop = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+        {
+            let stop = stop.clone();
+            let host_info = host_info(osc_port);
+            let tree = address_tree(addresses);
+
+            std::thread::spawn(move || serve(server, &stop, &host_info, &tree));
+        }
+
+        let mdns = advertise(osc_port, http_port)
+            .inspect_err(|e| warn!("Failed to advertise OSCQuery service: {:?}", e))
+            .ok();
+
+        Ok(Self { stop, mdns })
+    }
+
+    pub fn stop(self) {
+        self.stop.store(true, std::sync::atomic::Ordering::SeqCst);
+
+        if let Some(mdns) = self.mdns {
+            mdns.shutdown()
+                .map(|_| ())
+                .unwrap_or_else(|e| debug!("Failed to shutdown mDNS daemon: {:?}", e));
+        }
+    }
+}
+
+fn serve(
+    server: tiny_http::Server,
+    stop: &std::sync::atomic::AtomicBool,
+    host_info: &serde_json::Value,
+    tree: &serde_json::Value,
+) {
+    while !stop.load(std::sync::atomic::Ordering::SeqCst) {
+        let request = match server.recv_timeout(std::time::Duration::from_millis(500)) {
+            Ok(Some(request)) => request,
+            Ok(None) => continue,
+            Err(e) => {
+                error!("OSCQuery server failed: {:?}", e);
+                return;
+            }
+        };
+
+        let body = respond(request.url(), host_info, tree);
+
+        let response = match body {
+            Some(body) => {
+                tiny_http::Response::from_string(body.to_string()).with_header(json_content_type())
+            }
+            None => tiny_http::Response::from_string("").with_status_code(404),
+        };
+
+        request
+            .respond(response)
+            .unwrap_or_else(|e| debug!("Failed to respond to OSCQuery request: {:?}", e));
+    }
+}
+
+fn json_content_type() -> tiny_http::Header {
+    tiny_http::Header::from_bytes("Content-Type", "application/json")
+        .expect("Content-Type header is valid")
+}
+
+fn respond(
+    url: &str,
+    host_info: &serde_json::Value,
+    tree: &serde_json::Value,
+) -> Option<serde_json::Value> {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+
+    if query == "HOST_INFO" {
+        return Some(host_info.clone());
+    }
+
+    let mut node = tree;
+
+    for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+        node = node.get("CONTENTS")?.get(segment)?;
+    }
+
+    Some(node.clone())
+}
+
+fn host_info(osc_port: u16) -> serde_json::Value {
+    serde_json::json!({
+        "NAME": SERVICE_NAME,
+        "OSC_IP": "127.0.0.1",
+        "OSC_PORT": osc_port,
+        "OSC_TRANSPORT": "UDP",
+        "EXTENSIONS": {
+            "ACCESS": true,
+            "VALUE": false,
+            "DESCRIPTION": true,
+        },
+    })
+}
+
+/// Builds the OSCQuery namespace. VRChat only sends avatar parameters to services that have `/avatar` in it.
+fn address_tree(addresses: &[String]) -> serde_json::Value {
+    let mut root = container_node("/");
+
+    for address in addresses {
+        let mut node = &mut root;
+        let mut full_path = String::new();
+        let segments: Vec<&str> = address
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect();
+
+        for (i, segment) in segments.iter().enumerate() {
+            full_path = format!("{}/{}", full_path, segment);
+
+            let child = if i == segments.len() - 1 {
+                serde_json::json!({
+                    "FULL_PATH": full_path,
+                    "ACCESS": ACCESS_WRITE_ONLY,
+                    "TYPE": "T",
+                    "DESCRIPTION": "Send to VRC action trigger",
+                })
+            } else {
+                container_node(&full_path)
+            };
+
+            node = node["CONTENTS"]
+                .as_object_mut()
+                .expect("container node has CONTENTS")
+                .entry(segment.to_string())
+                .or_insert(child);
+
+            if node.get("CONTENTS").is_none() {
+                break;
+            }
+        }
+    }
+
+    root
+}
+
+fn container_node(full_path: &str) -> serde_json::Value {
+    serde_json::json!({
+        "FULL_PATH": full_path,
+        "ACCESS": ACCESS_NONE,
+        "CONTENTS": {},
+    })
+}
+
+fn advertise(osc_port: u16, http_port: u16) -> Result<mdns_sd::ServiceDaemon, AppError> {
+    let mdns = mdns_sd::ServiceDaemon::new()
+        .map_err(AppError::from_error_with_message("Failed to start mDNS"))?;
+
+    // NOTE: Instance names must be unique on the network, so the port is appended.
+    let instance_name = format!("SendToVRC-{}", http_port);
+    let host_name = format!("{}.local.", instance_name);
+
+    for (service_type, port) in [
+        ("_oscjson._tcp.local.", http_port),
+        ("_osc._udp.local.", osc_port),
+    ] {
+        let service = mdns_sd::ServiceInfo::new(
+            service_type,
+            &instance_name,
+            &host_name,
+            "127.0.0.1",
+            port,
+            None::<std::collections::HashMap<String, String>>,
+        )
+        .map_err(AppError::from_error_with_message(
+            "Failed to create mDNS service info",
+        ))?;
+
+        mdns.register(service)
+            .map_err(AppError::from_error_with_message(
+                "Failed to register mDNS service",
+            ))?;
+    }
+
+    Ok(mdns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addresses() -> Vec<String> {
+        vec![
+            "/avatar/parameters/S2V_Capture".to_string(),
+            "/avatar/parameters/S2V_PrintLast".to_string(),
+        ]
+    }
+
+    #[test]
+    fn builds_address_tree() {
+        let tree = address_tree(&addresses());
+
+        let parameters = &tree["CONTENTS"]["avatar"]["CONTENTS"]["parameters"];
+        assert_eq!(parameters["FULL_PATH"], "/avatar/parameters");
+        assert_eq!(
+            parameters["CONTENTS"]["S2V_Capture"]["FULL_PATH"],
+            "/avatar/parameters/S2V_Capture"
+        );
+        assert_eq!(
+            parameters["CONTENTS"]["S2V_PrintLast"]["ACCESS"],
+            ACCESS_WRITE_ONLY
+        );
+    }
+
+    #[test]
+    fn responds_to_queries() {
+        let host_info = host_info(9001);
+        let tree = address_tree(&addresses());
+
+        assert_eq!(
+            respond("/?HOST_INFO", &host_info, &tree).unwrap()["OSC_PORT"],
+            9001
+        );
+        assert_eq!(
+            respond("/avatar", &host_info, &tree).unwrap()["FULL_PATH"],
+            "/avatar"
+        );
+        assert_eq!(respond("/", &host_info, &tree), Some(tree.clone()));
+        assert_eq!(respond("/unknown", &host_info, &tree), None);
+    }
+
+    #[tokio::test]
+    async fn serves_host_info_over_http() {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let port = server.server_addr().to_ip().unwrap().port();
+        let stop = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+        let thread = {
+            let stop = stop.clone();
+            std::thread::spawn(move || {
+                serve(server, &stop, &host_info(9001), &address_tree(&addresses()))
+            })
+        };
+
+        let response = reqwest::get(format!("http://127.0.0.1:{}/?HOST_INFO", port))
+            .await
+            .unwrap()
+            .json::<serde_json::Value>()
+            .await
+            .unwrap();
+
+        assert_eq!(response["OSC_TRANSPORT"], "UDP");
+
+        stop.store(true, std::sync::atomic::Ordering::SeqCst);
+        thread.join().unwrap();
+    }
+}
@@ -0,0 +1,110 @@
+use crate::prelude::*;
+
+/// Renders the image exactly as it would be uploaded for `mode` and returns the path to the rendered PNG.
+#[tauri::command]
+#[specta::specta]
+pub async fn render_send_preview(
+    file_path: &str,
+    mode: crate::SendRequestEventMode,
+) -> Result<String, AppError> {
+    let preview_path = preview_file_path(&mode).to_string_lossy().into_owned();
+
+    let input = crate::remote_image::resolve_input(file_path).await?;
+
+    render_send_preview_to(input.path(), &mode, &preview_path)?;
+
+    Ok(preview_path)
+}
+
+pub fn render_send_preview_to(
+    file_path: &str,
+    mode: &crate::SendRequestEventMode,
+    output_path: &str,
+) -> Result<(), AppError> {
+    if !std::path::Path::new(file_path).exists() {
+        return Err(AppError::Unknown("File does not exist".to_string()));
+    }
+
+    match mode {
+        crate::SendRequestEventMode::UploadImageToVideoServer => {
+            crate::image_to_video::render_video_frame(file_path, output_path)
+        }
+        crate::SendRequestEventMode::UploadImageToImageServer => {
+            crate::image_to_image::render_image_server_image(file_path, output_path)
+        }
+        crate::SendRequestEventMode::UploadImageToVRChatPrint => {
+            crate::vrchat_print::resize_image_vrchat_print(file_path, output_path)
+        }
+    }
+}
+
+fn preview_file_path(mode: &crate::SendRequestEventMode) -> std::path::PathBuf {
+    let name = match mode {
+        crate::SendRequestEventMode::UploadImageToVideoServer => "preview_video_frame.png",
+        crate::SendRequestEventMode::UploadImageToImageServer => "preview_image_server.png",
+        crate::SendRequestEventMode::UploadImageToVRChatPrint => "preview_vrchat_print.png",
+    };
+
+    crate::file::temp_file_path(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(mode: crate::SendRequestEventMode, name: &str) -> (u32, u32) {
+        let input_file =
+            std::env::var("CARGO_MANIFEST_DIR").unwrap() + "/test_data/input_image.png";
+
+        let output_file = crate::file::temp_file_path(name)
+            .to_string_lossy()
+            .into_owned();
+
+        render_send_preview_to(&input_file, &mode, &output_file).unwrap();
+
+        image::image_dimensions(&output_file).unwrap()
+    }
+
+    #[test]
+    fn renders_vrchat_print_canvas() {
+        assert_eq!(
+            render(
+                crate::SendRequestEventMode::UploadImageToVRChatPrint,
+                "test_preview_vrchat_print.png"
+            ),
+            (2048, 1440)
+        );
+    }
+
+    #[test]
+    fn renders_letterboxed_video_frame() {
+        assert_eq!(
+            render(
+                crate::SendRequestEventMode::UploadImageToVideoServer,
+                "test_preview_video_frame.png"
+            ),
+            (1280, 720)
+        );
+    }
+
+    #[test]
+    fn renders_image_server_image_within_bounds() {
+        let (width, height) = render(
+            crate::SendRequestEventMode::UploadImageToImageServer,
+            "test_preview_image_server.png",
+        );
+
+        assert!(width <= 1920 && height <= 1920);
+    }
+
+    #[test]
+    fn rejects_missing_file() {
+        let result = render_send_preview_to(
+            "does_not_exist.png",
+            &crate::SendRequestEventMode::UploadImageToVRChatPrint,
+            &crate::file::temp_file_path("test_preview_missing.png").to_string_lossy(),
+        );
+
+        assert!(result.is_err());
+    }
+}
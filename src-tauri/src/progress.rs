@@ -1,7 +1,7 @@
 use crate::prelude::*;
 use tauri::Emitter as _;
 
-#[derive(Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub enum Progress {
     Starting,
     Compressing,
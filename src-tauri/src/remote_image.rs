@@ -0,0 +1,300 @@
+use crate::prelude::*;
+
+const MAX_DOWNLOAD_BYTES: u64 = 50 * 1024 * 1024;
+const DOWNLOAD_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+const CHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+const ALLOWED_CONTENT_TYPES: [&str; 7] = [
+    "image/png",
+    "image/jpeg",
+    "image/gif",
+    "image/webp",
+    "image/bmp",
+    "image/avif",
+    "image/tiff",
+];
+
+pub fn is_remote_url(input: &str) -> bool {
+    let lowercase = input.to_ascii_lowercase();
+    lowercase.starts_with("https://") || lowercase.starts_with("http://")
+}
+
+/// A local file to feed into the pipelines. Downloaded files are removed on drop.
+pub struct LocalInput {
+    path: String,
+    workspace: Option<std::path::PathBuf>,
+}
+
+impl LocalInput {
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl Drop for LocalInput {
+    fn drop(&mut self) {
+        if let Some(workspace) = &self.workspace {
+            std::fs::remove_dir_all(workspace)
+                .unwrap_or_else(|e| debug!("Failed to remove download workspace: {:?}", e));
+        }
+    }
+}
+
+/// Downloads `input` into a job workspace if it is a URL, otherwise uses it as is.
+pub async fn resolve_input(input: &str) -> Result<LocalInput, AppError> {
+    if !is_remote_url(input) {
+        return Ok(LocalInput {
+            path: input.to_string(),
+            workspace: None,
+        });
+    }
+
+    let path = download_image(input, MAX_DOWNLOAD_BYTES).await?;
+
+    Ok(LocalInput {
+        path: path.to_string_lossy().into_owned(),
+        workspace: path.parent().map(|workspace| workspace.to_path_buf()),
+    })
+}
+
+/// Checks with a HEAD request that `url` points to an image, without downloading it.
+pub async fn is_remote_image(url: &str) -> bool {
+    let Ok(client) = reqwest::Client::builder().timeout(CHECK_TIMEOUT).build() else {
+        return false;
+    };
+
+    let response = match client.head(url).send().await {
+        Ok(response) => response,
+        Err(e) => {
+            debug!("Failed to check remote image: {:?}", e);
+            return false;
+        }
+    };
+
+    // NOTE: Some servers do not support HEAD. The download checks the image again anyway.
+    if response.status() == reqwest::StatusCode::METHOD_NOT_ALLOWED
+        || response.status() == reqwest::StatusCode::NOT_IMPLEMENTED
+    {
+        return true;
+    }
+
+    // NOTE: `Response::content_length` is the size of the (empty) HEAD body, so the header is read directly.
+    let content_length = response
+        .headers()
+        .get(reqwest::header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+
+    response.status().is_success()
+        && ALLOWED_CONTENT_TYPES.contains(&content_type_of(response.headers()).as_str())
+        && content_length.is_none_or(|length| length <= MAX_DOWNLOAD_BYTES)
+}
+
+/// The media type of a response, without parameters such as `charset`.
+fn content_type_of(headers: &reqwest::header::HeaderMap) -> String {
+    headers
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_ascii_lowercase())
+        .unwrap_or_default()
+}
+
+async fn download_image(url: &str, max_bytes: u64) -> Result<std::path::PathBuf, AppError> {
+    info!("Downloading image from {}", url);
+
+    let client = reqwest::Client::builder()
+        .timeout(DOWNLOAD_TIMEOUT)
+        .build()
+        .map_err(AppError::from_error_with_message(
+            "Failed to create HTTP client",
+        ))?;
+
+    let mut response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(AppError::from_error_with_message(
+            "Failed to download image",
+        ))?;
+
+    if !response.status().is_success() {
+        return Err(AppError::Unknown(format!(
+            "Failed to download image: HTTP {}",
+            response.status()
+        )));
+    }
+
+    let content_type = content_type_of(response.headers());
+
+    if !ALLOWED_CONTENT_TYPES.contains(&content_type.as_str()) {
+        return Err(AppError::Unknown(format!(
+            "URL is not an image (Content-Type: {})",
+            content_type
+        )));
+    }
+
+    let too_large = || {
+        AppError::Unknown(format!(
+            "Image is larger than {} MB",
+            max_bytes / 1024 / 1024
+        ))
+    };
+
+    if response
+        .content_length()
+        .is_some_and(|length| length > max_bytes)
+    {
+        return Err(too_large());
+    }
+
+    // NOTE: Content-Length may be missing or wrong, so the limit is also enforced while reading.
+    let mut bytes = Vec::new();
+
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(AppError::from_error_with_message(
+            "Failed to download image",
+        ))?
+    {
+        if (bytes.len() + chunk.len()) as u64 > max_bytes {
+            return Err(too_large());
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    crate::jobs::save_image_bytes_to_workspace(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_image() -> Vec<u8> {
+        std::fs::read(std::env::var("CARGO_MANIFEST_DIR").unwrap() + "/test_data/input_image.png")
+            .unwrap()
+    }
+
+    /// Serves one response per request, in order, and returns the base URL.
+    fn serve(responses: Vec<tiny_http::ResponseBox>) -> String {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let port = server.server_addr().to_ip().unwrap().port();
+
+        std::thread::spawn(move || {
+            for response in responses {
+                let request = server.recv().unwrap();
+                request.respond(response).unwrap();
+            }
+        });
+
+        format!("http://127.0.0.1:{}", port)
+    }
+
+    fn content_type(value: &str) -> tiny_http::Header {
+        tiny_http::Header::from_bytes("Content-Type", value).unwrap()
+    }
+
+    #[test]
+    fn detects_remote_urls() {
+        assert!(is_remote_url("https://cdn.discordapp.com/a.png"));
+        assert!(is_remote_url("HTTP://example.com/a.png"));
+        assert!(!is_remote_url("C:\\Users\\a.png"));
+        assert!(!is_remote_url("file:///tmp/a.png"));
+    }
+
+    #[tokio::test]
+    async fn downloads_image_and_removes_it_on_drop() {
+        let base_url = serve(vec![tiny_http::Response::from_data(test_image())
+            .with_header(content_type("image/png"))
+            .boxed()]);
+
+        let input = resolve_input(&format!("{}/a.png", base_url)).await.unwrap();
+        let path = std::path::PathBuf::from(input.path());
+
+        assert!(image::open(&path).is_ok());
+
+        drop(input);
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn keeps_local_paths() {
+        let input = resolve_input("/tmp/a.png").await.unwrap();
+
+        assert_eq!(input.path(), "/tmp/a.png");
+    }
+
+    #[tokio::test]
+    async fn rejects_non_images() {
+        let base_url = serve(vec![
+            tiny_http::Response::from_string("<html></html>")
+                .with_header(content_type("text/html; charset=utf-8"))
+                .boxed(),
+            tiny_http::Response::from_string("not really a png")
+                .with_header(content_type("image/png"))
+                .boxed(),
+            tiny_http::Response::from_string("")
+                .with_status_code(404)
+                .boxed(),
+        ]);
+
+        for _ in 0..3 {
+            assert!(
+                download_image(&format!("{}/a.png", base_url), MAX_DOWNLOAD_BYTES)
+                    .await
+                    .is_err()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn checks_remote_images_without_downloading() {
+        let base_url = serve(vec![
+            tiny_http::Response::from_data(test_image())
+                .with_header(content_type("image/png"))
+                .boxed(),
+            tiny_http::Response::from_string("<html></html>")
+                .with_header(content_type("text/html; charset=utf-8"))
+                .boxed(),
+            tiny_http::Response::from_string("")
+                .with_status_code(404)
+                .boxed(),
+            tiny_http::Response::from_string("")
+                .with_status_code(405)
+                .boxed(),
+        ]);
+        let url = format!("{}/a.png", base_url);
+
+        assert!(is_remote_image(&url).await);
+        assert!(!is_remote_image(&url).await);
+        assert!(!is_remote_image(&url).await);
+        assert!(is_remote_image(&url).await);
+    }
+
+    #[tokio::test]
+    async fn rejects_too_large_images() {
+        let image = test_image();
+        let limit = image.len() as u64 - 1;
+
+        let base_url = serve(vec![
+            tiny_http::Response::from_data(image.clone())
+                .with_header(content_type("image/png"))
+                .boxed(),
+            // Without Content-Length
+            tiny_http::Response::new(
+                tiny_http::StatusCode(200),
+                vec![content_type("image/png")],
+                std::io::Cursor::new(image),
+                None,
+                None,
+            )
+            .boxed(),
+        ]);
+
+        for _ in 0..2 {
+            assert!(download_image(&format!("{}/a.png", base_url), limit)
+                .await
+                .is_err());
+        }
+    }
+}
@@ -0,0 +1,392 @@
+use tauri::Manager as _;
+use tauri_specta::Event as _;
+
+use crate::prelude::*;
+
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+const DEFAULT_PATTERNS: &str = "VRChat_*.png";
+
+#[derive(Debug, Clone, PartialEq)]
+struct WatcherSettings {
+    directory: std::path::PathBuf,
+    patterns: Vec<String>,
+    action: crate::capture::FinishCaptureNextAction,
+    auto_send: bool,
+}
+
+impl WatcherSettings {
+    fn load(handle: &tauri::AppHandle) -> Option<Self> {
+        let state = handle.state::<crate::app_data::AppData>();
+
+        if !state.is_healthy() {
+            return None;
+        }
+
+        let config = state.lock_config();
+
+        if !config.get_bool("screenshot_watcher_enabled", false) {
+            return None;
+        }
+
+        let (directory, patterns) = screenshot_source(handle, &config)?;
+
+        let action = match config.get("screenshot_watcher_action") {
+            Some("UploadImageToVideoServer") => {
+                crate::capture::FinishCaptureNextAction::UploadImageToVideoServer
+            }
+            Some("UploadImageToImageServer") => {
+                crate::capture::FinishCaptureNextAction::UploadImageToImageServer
+            }
+            Some("UploadImageToVRChatPrint") => {
+                crate::capture::FinishCaptureNextAction::UploadImageToVRChatPrint
+            }
+            _ => crate::capture::FinishCaptureNextAction::Ask,
+        };
+
+        let auto_send_key = match action {
+            crate::capture::FinishCaptureNextAction::Ask => None,
+            crate::capture::FinishCaptureNextAction::UploadImageToVideoServer => {
+                Some("screenshot_watcher_auto_send_video")
+            }
+            crate::capture::FinishCaptureNextAction::UploadImageToImageServer => {
+                Some("screenshot_watcher_auto_send_image")
+            }
+            crate::capture::FinishCaptureNextAction::UploadImageToVRChatPrint => {
+                Some("screenshot_watcher_auto_send_print")
+            }
+        };
+
+        let auto_send = auto_send_key.is_some_and(|key| config.get_bool(key, false));
+
+        Some(Self {
+            directory,
+            patterns,
+            action,
+            auto_send,
+        })
+    }
+}
+
+fn screenshot_source(
+    handle: &tauri::AppHandle,
+    config: &crate::config::Config,
+) -> Option<(std::path::PathBuf, Vec<String>)> {
+    let directory = match config.get("screenshot_watcher_directory") {
+        Some(directory) if !directory.is_empty() => std::path::PathBuf::from(directory),
+        _ => default_screenshot_directory(handle.clone()).ok()?,
+    };
+
+    let patterns = config
+        .get("screenshot_watcher_patterns")
+        .filter(|patterns| !patterns.trim().is_empty())
+        .unwrap_or(DEFAULT_PATTERNS)
+        .split(',')
+        .map(|pattern| pattern.trim().to_string())
+        .filter(|pattern| !pattern.is_empty())
+        .collect();
+
+    Some((directory, patterns))
+}
+
+/// Returns the most recently modified screenshot in the configured folder, even if the watcher is disabled.
+pub fn latest_screenshot(handle: &tauri::AppHandle) -> Option<std::path::PathBuf> {
+    let (directory, patterns) = {
+        let state = handle.state::<crate::app_data::AppData>();
+        screenshot_source(handle, &state.lock_config())?
+    };
+
+    find_screenshots(&directory, &patterns)
+        .into_iter()
+        .filter_map(|path| {
+            let modified = std::fs::metadata(&path).ok()?.modified().ok()?;
+            Some((modified, path))
+        })
+        .max()
+        .map(|(_, path)| path)
+}
+
+#[derive(Debug, Clone, serde::Serialize, specta::Type, tauri_specta::Event)]
+pub struct ScreenshotDetectedEvent {
+    file: String,
+    auto_send: bool,
+}
+
+pub fn init_screenshot_watcher(app_handle: tauri::AppHandle) {
+    std::thread::spawn(move || screenshot_watcher_thread(app_handle));
+}
+
+fn screenshot_watcher_thread(app_handle: tauri::AppHandle) {
+    let mut watching: Option<(WatcherSettings, NewFileDetector)> = None;
+
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+
+        let settings = WatcherSettings::load(&app_handle);
+
+        // Restart from a fresh snapshot whenever the settings change, so existing photos are never sent.
+        if watching.as_ref().map(|(current, _)| current) != settings.as_ref() {
+            watching = settings.map(|settings| {
+                info!("Watching screenshots in {:?}", settings.directory);
+                let detector = NewFileDetector::new(&settings.directory, &settings.patterns);
+                (settings, detector)
+            });
+            continue;
+        }
+
+        let Some((settings, detector)) = &mut watching else {
+            continue;
+        };
+
+        for file in detector.poll() {
+            info!("New screenshot detected: {:?}", file);
+            handle_new_screenshot(&app_handle, settings, &file.to_string_lossy());
+        }
+    }
+}
+
+fn handle_new_screenshot(app_handle: &tauri::AppHandle, settings: &WatcherSettings, file: &str) {
+    ScreenshotDetectedEvent {
+        file: file.to_string(),
+        auto_send: settings.auto_send,
+    }
+    .emit(app_handle)
+    .unwrap_or_else(|e| debug!("Failed to emit screenshot detected event: {:?}", e));
+
+    let mode = crate::capture::send_request_event_mode(&settings.action);
+
+    if let (true, Some(mode)) = (settings.auto_send, &mode) {
+        // NOTE: Sending here (not in a spawned task) keeps auto-sent screenshots in order.
+        if let Err(e) = tauri::async_runtime::block_on(crate::send::send_file_and_report(
+            app_handle,
+            file,
+            mode.clone(),
+        )) {
+            error!("Failed to auto-send screenshot {}: {:?}", file, e);
+        }
+        return;
+    }
+
+    crate::SendRequestEvent {
+        file: file.to_string(),
+        mode,
+        preselected_mode: None,
+        copy: None,
+        saved_files: Vec::new(),
+    }
+    .emit(app_handle)
+    .unwrap_or_else(|e| debug!("Failed to emit send request event: {:?}", e));
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn default_screenshot_directory(
+    handle: tauri::AppHandle,
+) -> Result<std::path::PathBuf, AppError> {
+    let picture_dir = handle
+        .path()
+        .picture_dir()
+        .map_err(AppError::from_error_with_message(
+            "Failed to get picture directory",
+        ))?;
+
+    Ok(picture_dir.join("VRChat"))
+}
+
+/// Finds files that appeared after the detector was created, once they are completely written.
+struct NewFileDetector {
+    directory: std::path::PathBuf,
+    patterns: Vec<String>,
+    seen: std::collections::HashSet<std::path::PathBuf>,
+    // Size observed on the previous poll. A file is complete when its size stops changing and it can be decoded.
+    pending: std::collections::HashMap<std::path::PathBuf, u64>,
+}
+
+impl NewFileDetector {
+    fn new(directory: &std::path::Path, patterns: &[String]) -> Self {
+        let mut detector = Self {
+            directory: directory.to_path_buf(),
+            patterns: patterns.to_vec(),
+            seen: std::collections::HashSet::new(),
+            pending: std::collections::HashMap::new(),
+        };
+
+        detector.seen = detector.matching_files().into_iter().collect();
+
+        detector
+    }
+
+    fn poll(&mut self) -> Vec<std::path::PathBuf> {
+        let mut completed = Vec::new();
+
+        for path in self.matching_files() {
+            if self.seen.contains(&path) {
+                continue;
+            }
+
+            let Ok(size) = std::fs::metadata(&path).map(|metadata| metadata.len()) else {
+                continue;
+            };
+
+            let previous_size = self.pending.insert(path.clone(), size);
+
+            if size == 0 || previous_size != Some(size) {
+                continue;
+            }
+
+            if image::open(&path).is_err() {
+                continue;
+            }
+
+            self.pending.remove(&path);
+            self.seen.insert(path.clone());
+            completed.push(path);
+        }
+
+        completed.sort();
+        completed
+    }
+
+    fn matching_files(&self) -> Vec<std::path::PathBuf> {
+        find_screenshots(&self.directory, &self.patterns)
+    }
+}
+
+fn find_screenshots(directory: &std::path::Path, patterns: &[String]) -> Vec<std::path::PathBuf> {
+    // NOTE: VRChat saves screenshots into monthly subdirectories (e.g. 2025-10).
+    let mut files = Vec::new();
+    let mut directories = vec![(directory.to_path_buf(), 0)];
+
+    while let Some((directory, depth)) = directories.pop() {
+        let Ok(entries) = std::fs::read_dir(&directory) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+
+            if path.is_dir() {
+                if depth < 1 {
+                    directories.push((path, depth + 1));
+                }
+                continue;
+            }
+
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+
+            if patterns
+                .iter()
+                .any(|pattern| matches_pattern(pattern, &file_name))
+            {
+                files.push(path);
+            }
+        }
+    }
+
+    files
+}
+
+/// Case-insensitive wildcard match supporting `*` and `?`.
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = backtrack {
+            p = star_p + 1;
+            n = star_n + 1;
+            backtrack = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_directory(name: &str) -> std::path::PathBuf {
+        let directory = std::env::temp_dir().join(format!("send_to_vrc_test_watcher_{}", name));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    fn copy_test_image(to: &std::path::Path) {
+        let input_file =
+            std::env::var("CARGO_MANIFEST_DIR").unwrap() + "/test_data/input_image.png";
+        std::fs::copy(input_file, to).unwrap();
+    }
+
+    #[test]
+    fn matches_wildcard_patterns() {
+        assert!(matches_pattern(
+            "VRChat_*.png",
+            "VRChat_2025-10-19_21-03-11.123_1920x1080.png"
+        ));
+        assert!(matches_pattern("vrchat_*.PNG", "VRChat_1.png"));
+        assert!(matches_pattern("*_????x????.png", "VRChat_1_1920x1080.png"));
+        assert!(!matches_pattern("VRChat_*.png", "VRChat_1.jpg"));
+        assert!(!matches_pattern("VRChat_*.png", "Other_1.png"));
+        assert!(matches_pattern("*", "anything"));
+    }
+
+    #[test]
+    fn ignores_existing_files() {
+        let directory = test_directory("ignores_existing_files");
+        copy_test_image(&directory.join("VRChat_old.png"));
+
+        let mut detector = NewFileDetector::new(&directory, &["VRChat_*.png".to_string()]);
+
+        assert!(detector.poll().is_empty());
+        assert!(detector.poll().is_empty());
+    }
+
+    #[test]
+    fn detects_new_files_once_written() {
+        let directory = test_directory("detects_new_files_once_written");
+        let monthly_directory = directory.join("2025-10");
+        std::fs::create_dir_all(&monthly_directory).unwrap();
+
+        let mut detector = NewFileDetector::new(&directory, &["VRChat_*.png".to_string()]);
+
+        let new_file = monthly_directory.join("VRChat_new.png");
+        copy_test_image(&new_file);
+        copy_test_image(&directory.join("Other.png"));
+
+        // first poll only records the size
+        assert!(detector.poll().is_empty());
+        assert_eq!(detector.poll(), vec![new_file]);
+        assert!(detector.poll().is_empty());
+    }
+
+    #[test]
+    fn waits_for_partially_written_files() {
+        let directory = test_directory("waits_for_partially_written_files");
+        let mut detector = NewFileDetector::new(&directory, &["*.png".to_string()]);
+
+        let input_file =
+            std::env::var("CARGO_MANIFEST_DIR").unwrap() + "/test_data/input_image.png";
+        let bytes = std::fs::read(input_file).unwrap();
+        let new_file = directory.join("VRChat_partial.png");
+
+        std::fs::write(&new_file, &bytes[..bytes.len() / 2]).unwrap();
+        assert!(detector.poll().is_empty());
+        assert!(detector.poll().is_empty()); // size is stable but the image cannot be decoded yet
+
+        std::fs::write(&new_file, &bytes).unwrap();
+        assert!(detector.poll().is_empty());
+        assert_eq!(detector.poll(), vec![new_file]);
+    }
+}
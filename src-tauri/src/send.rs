@@ -0,0 +1,275 @@
+use tauri::Manager as _;
+use tauri_specta::Event as _;
+
+use crate::prelude::*;
+
+const SEND_MODE_HEADER: &str = "mode";
+
+static LAST_SENT_FILE: std::sync::Mutex<Option<String>> = std::sync::Mutex::new(None);
+
+pub fn remember_sent_file(file_path: &str) {
+    *LAST_SENT_FILE.lock().unwrap() = Some(file_path.to_string());
+}
+
+pub fn last_sent_file() -> Option<String> {
+    LAST_SENT_FILE.lock().unwrap().clone()
+}
+
+/// Everything needed to run the upload pipelines without the frontend.
+pub struct SendContext {
+    pub ffmpeg_path: String,
+    pub config: std::sync::Arc<std::sync::Mutex<crate::config::Config>>,
+    pub progress_callback: Option<progress::ProgressCallback>,
+    pub queue_callback: Option<crate::vrchat_queue::QueueCallback>,
+}
+
+impl SendContext {
+    pub fn from_app_handle(handle: &tauri::AppHandle) -> Result<Self, AppError> {
+        Ok(Self {
+            ffmpeg_path: crate::image_to_video::resolve_ffmpeg_path(handle)?,
+            config: handle.state::<crate::app_data::AppData>().try_config()?,
+            progress_callback: Some(progress::create_progress_callback(handle)),
+            queue_callback: Some(crate::vrchat_queue::create_queue_callback(handle)),
+        })
+    }
+
+    fn uploader_settings(&self) -> Result<(String, String), AppError> {
+        let config = self.config.lock().unwrap();
+
+        let Some(api_key) = config.get("uploader_api_key").filter(|key| !key.is_empty()) else {
+            return Err(AppError::UploaderAuthRequired(
+                "Uploader API key is not set in configuration".to_string(),
+            ));
+        };
+
+        let base_url = config
+            .get("uploader_url_base")
+            .unwrap_or(crate::uploader::DEFAULT_UPLOADER_URL_BASE);
+
+        Ok((api_key.to_string(), base_url.to_string()))
+    }
+}
+
+/// Sends `file_path` with `mode` and returns the uploaded URL. VRChat Print has no URL.
+///
+/// `file_path` may also be an http(s) URL, which is downloaded into a job workspace first.
+pub async fn send_file(
+    context: &SendContext,
+    file_path: &str,
+    mode: &crate::SendRequestEventMode,
+) -> Result<Option<String>, AppError> {
+    let input = crate::remote_image::resolve_input(file_path).await?;
+
+    let url = run_send_pipeline(context, input.path(), mode).await?;
+
+    remember_sent_file(file_path);
+
+    if let Some(url) = &url {
+        crate::osc::post_url_to_chatbox(&context.config.lock().unwrap(), url);
+    }
+
+    Ok(url)
+}
+
+// NOTE: Intermediate files go to a workspace per send, so sends running at the same time do not overwrite each other's.
+async fn run_send_pipeline(
+    context: &SendContext,
+    file_path: &str,
+    mode: &crate::SendRequestEventMode,
+) -> Result<Option<String>, AppError> {
+    let workspace = crate::jobs::create_workspace()?;
+
+    let result = run_send_pipeline_in(context, file_path, mode, &workspace).await;
+
+    crate::jobs::remove_workspace(&workspace);
+
+    result
+}
+
+async fn run_send_pipeline_in(
+    context: &SendContext,
+    file_path: &str,
+    mode: &crate::SendRequestEventMode,
+    workspace: &std::path::Path,
+) -> Result<Option<String>, AppError> {
+    match mode {
+        crate::SendRequestEventMode::UploadImageToVideoServer => {
+            let (api_key, uploader_base_url) = context.uploader_settings()?;
+
+            crate::image_to_video::upload_image_to_video_server_internal(
+                &context.ffmpeg_path,
+                file_path,
+                &api_key,
+                &uploader_base_url,
+                workspace,
+                context.progress_callback.as_ref(),
+            )
+            .await
+            .map(Some)
+        }
+        crate::SendRequestEventMode::UploadImageToImageServer => {
+            let (api_key, uploader_base_url) = context.uploader_settings()?;
+
+            crate::image_to_image::upload_image_to_image_server_internal(
+                file_path,
+                &api_key,
+                &uploader_base_url,
+                workspace,
+                context.progress_callback.as_ref(),
+            )
+            .await
+            .map(Some)
+        }
+        crate::SendRequestEventMode::UploadImageToVRChatPrint => {
+            let vrchat_api_key =
+                crate::vrchat_print::read_vrchat_api_key(&context.config.lock().unwrap())?;
+
+            crate::vrchat_print::upload_image_to_vrchat_print_internal(
+                file_path,
+                &vrchat_api_key,
+                &workspace.join("letterboxed_image.png"),
+                context.queue_callback.as_ref(),
+            )
+            .await
+            .map(|_| None)
+        }
+    }
+}
+
+/// Sends image bytes (e.g. pasted or dropped in the frontend) without a file on disk.
+///
+/// The bytes are the raw request body and the mode is in the `mode` header, so images are not
+/// serialized as a JSON array. tauri-specta cannot describe a raw body, so this command is
+/// registered on its own and called through `sendImageBytes` in `src/ipc.ts`.
+#[tauri::command]
+pub async fn send_image_bytes(
+    handle: tauri::AppHandle,
+    request: tauri::ipc::Request<'_>,
+) -> Result<Option<String>, AppError> {
+    let tauri::ipc::InvokeBody::Raw(bytes) = request.body() else {
+        return Err(AppError::Unknown(
+            "Image bytes must be sent as a raw body".to_string(),
+        ));
+    };
+
+    let mode = request
+        .headers()
+        .get(SEND_MODE_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .ok_or(AppError::Unknown(format!(
+            "Missing {} header",
+            SEND_MODE_HEADER
+        )))?;
+
+    let mode = serde_json::from_value(serde_json::Value::String(mode.to_string()))
+        .map_err(AppError::from_error_with_message("Unknown send mode"))?;
+
+    let path = crate::jobs::save_image_bytes_to_workspace(bytes)?;
+
+    send_job(&handle, &path.to_string_lossy(), mode).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn send_clipboard_image(
+    handle: tauri::AppHandle,
+    mode: crate::SendRequestEventMode,
+) -> Result<Option<String>, AppError> {
+    let bytes = crate::clipboard::read_clipboard_image(&handle)?;
+
+    let path = crate::jobs::save_image_bytes_to_workspace(&bytes)?;
+
+    send_job(&handle, &path.to_string_lossy(), mode).await
+}
+
+// NOTE: The workspace is kept with the job so the history can still refer to the file.
+async fn send_job(
+    handle: &tauri::AppHandle,
+    file_path: &str,
+    mode: crate::SendRequestEventMode,
+) -> Result<Option<String>, AppError> {
+    let job_id = crate::jobs::start_job(file_path, &mode);
+
+    run_job(handle, job_id, file_path, &mode).await
+}
+
+#[derive(Debug, Clone, serde::Serialize, specta::Type)]
+#[serde(tag = "type", content = "content")]
+pub enum SendOutcome {
+    Sent(Option<String>),
+    Failed(AppError),
+}
+
+/// Reports the result of a send that was started by the backend (e.g. folder watcher), not by the frontend.
+#[derive(Debug, Clone, serde::Serialize, specta::Type, tauri_specta::Event)]
+pub struct SendResultEvent {
+    file: String,
+    mode: crate::SendRequestEventMode,
+    outcome: SendOutcome,
+}
+
+pub async fn send_file_and_report(
+    handle: &tauri::AppHandle,
+    file_path: &str,
+    mode: crate::SendRequestEventMode,
+) -> Result<Option<String>, AppError> {
+    let job_id = crate::jobs::start_job(file_path, &mode);
+
+    run_job_and_report(handle, job_id, file_path, mode).await
+}
+
+/// Starts sending in the background and returns the job id to poll.
+pub fn spawn_send_job(
+    handle: &tauri::AppHandle,
+    file_path: String,
+    mode: crate::SendRequestEventMode,
+) -> u32 {
+    let job_id = crate::jobs::start_job(&file_path, &mode);
+    let handle = handle.clone();
+
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = run_job_and_report(&handle, job_id, &file_path, mode).await {
+            error!("Failed to send {}: {:?}", file_path, e);
+        }
+    });
+
+    job_id
+}
+
+async fn run_job_and_report(
+    handle: &tauri::AppHandle,
+    job_id: u32,
+    file_path: &str,
+    mode: crate::SendRequestEventMode,
+) -> Result<Option<String>, AppError> {
+    let result = run_job(handle, job_id, file_path, &mode).await;
+
+    SendResultEvent {
+        file: file_path.to_string(),
+        mode,
+        outcome: match &result {
+            Ok(url) => SendOutcome::Sent(url.clone()),
+            Err(error) => SendOutcome::Failed(error.clone()),
+        },
+    }
+    .emit(handle)
+    .unwrap_or_else(|e| debug!("Failed to emit send result event: {:?}", e));
+
+    result
+}
+
+async fn run_job(
+    handle: &tauri::AppHandle,
+    job_id: u32,
+    file_path: &str,
+    mode: &crate::SendRequestEventMode,
+) -> Result<Option<String>, AppError> {
+    let result = match SendContext::from_app_handle(handle) {
+        Ok(context) => send_file(&context, file_path, mode).await,
+        Err(error) => Err(error),
+    };
+
+    crate::jobs::finish_job(job_id, &result);
+
+    result
+}
@@ -0,0 +1,458 @@
+use tauri::Manager as _;
+use tauri_specta::Event as _;
+use tokio::io::{AsyncBufReadExt as _, AsyncWriteExt as _};
+
+use crate::launch_options::{LaunchOptions, LaunchOptionsMode};
+use crate::prelude::*;
+
+// Protocol: the second instance writes its `LaunchOptions` as one JSON line and waits for this line.
+const ACKNOWLEDGEMENT: &str = "OK";
+const FORWARD_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+
+// NOTE: Each profile runs its own instance, so launches with another profile are not forwarded.
+fn instance_name() -> String {
+    let user_name = std::env::var("USERNAME")
+        .or_else(|_| std::env::var("USER"))
+        .unwrap_or("default".to_string());
+
+    match crate::config::profile() {
+        Some(profile) => format!("send-to-vrc-{}-{}", user_name, profile),
+        None => format!("send-to-vrc-{}", user_name),
+    }
+}
+
+#[cfg(windows)]
+pub fn default_endpoint() -> String {
+    format!(r"\\.\pipe\{}", instance_name())
+}
+
+#[cfg(unix)]
+pub fn default_endpoint() -> String {
+    dirs::runtime_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join(format!("{}.sock", instance_name()))
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Hands `launch_options` over to the running instance. Returns false if there is none.
+pub fn forward_to_running_instance(launch_options: LaunchOptions) -> bool {
+    let Ok(runtime) = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    else {
+        return false;
+    };
+
+    let launch_options = with_absolute_paths(launch_options);
+
+    runtime
+        .block_on(async {
+            tokio::time::timeout(
+                FORWARD_TIMEOUT,
+                forward(&default_endpoint(), &launch_options),
+            )
+            .await
+        })
+        .is_ok_and(|result| result.is_ok())
+}
+
+// NOTE: The running instance may have a different working directory.
+fn with_absolute_paths(launch_options: LaunchOptions) -> LaunchOptions {
+    let LaunchOptionsMode::Send { files, options } = launch_options.mode else {
+        return launch_options;
+    };
+
+    let files = files
+        .into_iter()
+        .map(|file| {
+            if crate::remote_image::is_remote_url(&file) {
+                return file;
+            }
+
+            std::path::absolute(&file)
+                .map(|path| path.to_string_lossy().into_owned())
+                .unwrap_or(file)
+        })
+        .collect();
+
+    LaunchOptions {
+        mode: LaunchOptionsMode::Send { files, options },
+    }
+}
+
+pub async fn forward(endpoint: &str, launch_options: &LaunchOptions) -> Result<(), AppError> {
+    let stream = connect(endpoint).await?;
+    let (reader, mut writer) = tokio::io::split(stream);
+
+    let mut message = serde_json::to_string(launch_options).map_err(
+        AppError::from_error_with_message("Failed to serialize launch options"),
+    )?;
+    message.push('\n');
+
+    writer
+        .write_all(message.as_bytes())
+        .await
+        .map_err(AppError::from_error_with_message(
+            "Failed to send launch options",
+        ))?;
+
+    let mut response = String::new();
+
+    tokio::io::BufReader::new(reader)
+        .read_line(&mut response)
+        .await
+        .map_err(AppError::from_error_with_message(
+            "Failed to receive acknowledgement",
+        ))?;
+
+    if response.trim_end() != ACKNOWLEDGEMENT {
+        return Err(AppError::Unknown(format!(
+            "Unexpected response from running instance: {:?}",
+            response
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+async fn connect(endpoint: &str) -> Result<tokio::net::UnixStream, AppError> {
+    tokio::net::UnixStream::connect(endpoint)
+        .await
+        .map_err(AppError::from_error_with_message(
+            "Failed to connect to running instance",
+        ))
+}
+
+#[cfg(windows)]
+async fn connect(
+    endpoint: &str,
+) -> Result<tokio::net::windows::named_pipe::NamedPipeClient, AppError> {
+    tokio::net::windows::named_pipe::ClientOptions::new()
+        .open(endpoint)
+        .map_err(AppError::from_error_with_message(
+            "Failed to connect to running instance",
+        ))
+}
+
+/// Receives `LaunchOptions` from instances started later.
+pub struct InstanceServer {
+    endpoint: String,
+    #[cfg(unix)]
+    listener: tokio::net::UnixListener,
+    #[cfg(windows)]
+    server: tokio::net::windows::named_pipe::NamedPipeServer,
+}
+
+impl InstanceServer {
+    #[cfg(unix)]
+    pub fn bind(endpoint: &str) -> Result<Self, AppError> {
+        // NOTE: The socket file is left behind when the app is killed. Only a refused connection means it is stale.
+        match std::os::unix::net::UnixStream::connect(endpoint) {
+            Ok(_) => {
+                return Err(AppError::Unknown(
+                    "Another instance is already listening".to_string(),
+                ));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::ConnectionRefused => {
+                std::fs::remove_file(endpoint).map_err(AppError::from_error_with_message(
+                    "Failed to remove stale socket",
+                ))?;
+            }
+            Err(_) => {}
+        }
+
+        let listener = tokio::net::UnixListener::bind(endpoint).map_err(
+            AppError::from_error_with_message("Failed to bind instance socket"),
+        )?;
+
+        Ok(Self {
+            endpoint: endpoint.to_string(),
+            listener,
+        })
+    }
+
+    #[cfg(windows)]
+    pub fn bind(endpoint: &str) -> Result<Self, AppError> {
+        let server = tokio::net::windows::named_pipe::ServerOptions::new()
+            .first_pipe_instance(true)
+            .create(endpoint)
+            .map_err(AppError::from_error_with_message(
+                "Failed to create instance pipe",
+            ))?;
+
+        Ok(Self {
+            endpoint: endpoint.to_string(),
+            server,
+        })
+    }
+
+    #[cfg(unix)]
+    pub async fn accept(&mut self) -> Result<LaunchOptions, AppError> {
+        let (stream, _) =
+            self.listener
+                .accept()
+                .await
+                .map_err(AppError::from_error_with_message(
+                    "Failed to accept instance connection",
+                ))?;
+
+        receive(stream).await
+    }
+
+    #[cfg(windows)]
+    pub async fn accept(&mut self) -> Result<LaunchOptions, AppError> {
+        self.server
+            .connect()
+            .await
+            .map_err(AppError::from_error_with_message(
+                "Failed to accept instance connection",
+            ))?;
+
+        // A pipe instance serves one client, so the next one has to exist before handling this.
+        let next = tokio::net::windows::named_pipe::ServerOptions::new()
+            .create(&self.endpoint)
+            .map_err(AppError::from_error_with_message(
+                "Failed to create instance pipe",
+            ))?;
+
+        receive(std::mem::replace(&mut self.server, next)).await
+    }
+}
+
+async fn receive<S>(stream: S) -> Result<LaunchOptions, AppError>
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+
+    let mut message = String::new();
+
+    tokio::io::BufReader::new(reader)
+        .read_line(&mut message)
+        .await
+        .map_err(AppError::from_error_with_message(
+            "Failed to receive launch options",
+        ))?;
+
+    let launch_options = serde_json::from_str(&message).map_err(
+        AppError::from_error_with_message("Failed to parse launch options"),
+    )?;
+
+    writer
+        .write_all(format!("{}\n", ACKNOWLEDGEMENT).as_bytes())
+        .await
+        .map_err(AppError::from_error_with_message(
+            "Failed to send acknowledgement",
+        ))?;
+
+    Ok(launch_options)
+}
+
+pub fn init_instance_server(app_handle: tauri::AppHandle) {
+    // NOTE: Binding needs the Tokio reactor, so it happens inside the task.
+    tauri::async_runtime::spawn(async move {
+        let mut server = match InstanceServer::bind(&default_endpoint()) {
+            Ok(server) => server,
+            Err(e) => {
+                error!("Failed to start instance server: {:?}", e);
+                return;
+            }
+        };
+
+        info!("Instance server is listening on {}", server.endpoint);
+
+        loop {
+            match server.accept().await {
+                Ok(launch_options) => handle_launch_options(&app_handle, launch_options),
+                Err(e) => warn!("Failed to receive launch options: {:?}", e),
+            }
+        }
+    });
+}
+
+pub fn handle_launch_options(app_handle: &tauri::AppHandle, launch_options: LaunchOptions) {
+    info!("Launch options from another instance: {:?}", launch_options);
+
+    focus_main_window(app_handle);
+
+    match launch_options.mode {
+        LaunchOptionsMode::Send { files, options } => {
+            for file in files {
+                crate::SendRequestEvent {
+                    file,
+                    mode: options.mode.clone(),
+                    preselected_mode: options.preselected_mode.clone(),
+                    copy: options.copy,
+                    saved_files: Vec::new(),
+                }
+                .emit(app_handle)
+                .unwrap_or_else(|e| debug!("Failed to emit send request event: {:?}", e));
+            }
+        }
+        LaunchOptionsMode::Capture => {
+            if let Err(e) = crate::capture::start_capture() {
+                error!("Failed to start capture: {}", e);
+            }
+        }
+        LaunchOptionsMode::Default | LaunchOptionsMode::Help | LaunchOptionsMode::Version => {}
+    }
+}
+
+fn focus_main_window(app_handle: &tauri::AppHandle) {
+    let Some(window) = app_handle.get_webview_window("main") else {
+        return;
+    };
+
+    window
+        .unminimize()
+        .and_then(|_| window.show())
+        .and_then(|_| window.set_focus())
+        .unwrap_or_else(|e| info!("Failed to focus main window: {:?}", e));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHILD_ENDPOINT_ENV: &str = "SEND_TO_VRC_TEST_INSTANCE_ENDPOINT";
+
+    #[cfg(windows)]
+    fn test_endpoint(name: &str) -> String {
+        format!(r"\\.\pipe\send-to-vrc-test-{}-{}", name, std::process::id())
+    }
+
+    #[cfg(unix)]
+    fn test_endpoint(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!(
+                "send-to-vrc-test-{}-{}.sock",
+                name,
+                std::process::id()
+            ))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn send_options(files: &[&str]) -> LaunchOptions {
+        LaunchOptions {
+            mode: LaunchOptionsMode::Send {
+                files: files.iter().map(|file| file.to_string()).collect(),
+                options: crate::launch_options::SendOptions {
+                    mode: Some(crate::SendRequestEventMode::UploadImageToVRChatPrint),
+                    ..Default::default()
+                },
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn forwards_launch_options() {
+        let endpoint = test_endpoint("forward");
+        let mut server = InstanceServer::bind(&endpoint).unwrap();
+
+        let launch_options = send_options(&["/tmp/a.png", "/tmp/b.png"]);
+
+        let (received, forwarded) =
+            tokio::join!(server.accept(), forward(&endpoint, &launch_options));
+
+        forwarded.unwrap();
+        assert_eq!(received.unwrap().mode, launch_options.mode);
+    }
+
+    #[tokio::test]
+    async fn fails_without_running_instance() {
+        let endpoint = test_endpoint("missing");
+
+        assert!(forward(&endpoint, &send_options(&["a.png"])).await.is_err());
+    }
+
+    #[test]
+    fn makes_forwarded_paths_absolute() {
+        let LaunchOptionsMode::Send { files, .. } =
+            with_absolute_paths(send_options(&["a.png"])).mode
+        else {
+            panic!("Expected send mode");
+        };
+
+        assert_eq!(
+            files,
+            vec![std::env::current_dir()
+                .unwrap()
+                .join("a.png")
+                .to_string_lossy()
+                .into_owned()]
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn replaces_stale_socket() {
+        let endpoint = test_endpoint("stale");
+        drop(std::os::unix::net::UnixListener::bind(&endpoint).unwrap());
+
+        let mut server = InstanceServer::bind(&endpoint).unwrap();
+
+        let (received, forwarded) = tokio::join!(
+            server.accept(),
+            forward(
+                &endpoint,
+                &LaunchOptions {
+                    mode: LaunchOptionsMode::Capture
+                }
+            )
+        );
+
+        forwarded.unwrap();
+        assert_eq!(received.unwrap().mode, LaunchOptionsMode::Capture);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn keeps_socket_of_running_instance() {
+        let endpoint = test_endpoint("running");
+        let _server = InstanceServer::bind(&endpoint).unwrap();
+
+        assert!(InstanceServer::bind(&endpoint).is_err());
+        assert!(std::path::Path::new(&endpoint).exists());
+    }
+
+    #[tokio::test]
+    async fn receives_from_another_process() {
+        let endpoint = test_endpoint("process");
+        let mut server = InstanceServer::bind(&endpoint).unwrap();
+
+        let mut child = tokio::process::Command::new(std::env::current_exe().unwrap())
+            .args([
+                "single_instance::tests::second_instance",
+                "--exact",
+                "--ignored",
+                "--nocapture",
+            ])
+            .env(CHILD_ENDPOINT_ENV, &endpoint)
+            .spawn()
+            .unwrap();
+
+        let received = tokio::time::timeout(std::time::Duration::from_secs(30), server.accept())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(received.mode, send_options(&["/tmp/from-child.png"]).mode);
+        assert!(child.wait().await.unwrap().success());
+    }
+
+    // Runs in the child process of `receives_from_another_process`.
+    #[tokio::test]
+    #[ignore]
+    async fn second_instance() {
+        let Ok(endpoint) = std::env::var(CHILD_ENDPOINT_ENV) else {
+            return;
+        };
+
+        forward(&endpoint, &send_options(&["/tmp/from-child.png"]))
+            .await
+            .unwrap();
+    }
+}
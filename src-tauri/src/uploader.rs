@@ -1,5 +1,8 @@
 use crate::prelude::*;
 
+// NOTE: Must be matched with the default value of `uploader_url_base` in the frontend.
+pub const DEFAULT_UPLOADER_URL_BASE: &str = "https://s2v-upload.superneko.net";
+
 #[derive(serde::Deserialize)]
 struct UploaderResponse {
     pub url: String,
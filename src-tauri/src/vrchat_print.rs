@@ -1,8 +1,10 @@
 use base64::Engine as _;
 use image::GenericImageView as _;
 use tauri::Manager as _;
+use tauri_specta::Event as _;
 
 use crate::prelude::*;
+use crate::vrchat_queue::VrchatRequestError;
 
 #[tauri::command]
 #[specta::specta]
@@ -10,11 +12,204 @@ pub async fn upload_image_to_vrchat_print(
     app_handle: tauri::AppHandle,
     file_path: &str,
 ) -> Result<(), AppError> {
-    let Some(vrchat_api_key) = app_handle
-        .state::<crate::app_data::AppData>()
-        .lock_config()
-        .read_vrchat_api_key()?
-    else {
+    let vrchat_api_key =
+        read_vrchat_api_key(&app_handle.state::<crate::app_data::AppData>().lock_config())?;
+
+    let queue_callback = Some(crate::vrchat_queue::create_queue_callback(&app_handle));
+
+    let input = crate::remote_image::resolve_input(file_path).await?;
+
+    let workspace = crate::jobs::create_workspace()?;
+
+    let result = upload_image_to_vrchat_print_internal(
+        input.path(),
+        &vrchat_api_key,
+        &workspace.join("letterboxed_image.png"),
+        queue_callback.as_ref(),
+    )
+    .await;
+
+    crate::jobs::remove_workspace(&workspace);
+
+    result?;
+
+    crate::send::remember_sent_file(file_path);
+
+    Ok(())
+}
+
+pub async fn upload_image_to_vrchat_print_internal(
+    file_path: &str,
+    vrchat_api_key: &str,
+    letterboxed_image_path: &std::path::Path,
+    queue_callback: Option<&crate::vrchat_queue::QueueCallback>,
+) -> Result<(), AppError> {
+    let path = std::path::Path::new(file_path);
+
+    if !path.exists() {
+        return Err(AppError::Unknown("File does not exist".to_string()));
+    }
+
+    resize_image_vrchat_print(file_path, &letterboxed_image_path.to_string_lossy())?;
+
+    let note = print_note(file_path);
+
+    crate::vrchat_queue::vrchat_request_queue()
+        .submit(queue_callback, || {
+            send_file_to_print(letterboxed_image_path, vrchat_api_key.to_string(), &note)
+        })
+        .await
+}
+
+fn print_note(file_path: &str) -> String {
+    let world_name = std::fs::read(file_path)
+        .ok()
+        .and_then(|bytes| crate::image::screenshot_metadata_from_png(&bytes))
+        .and_then(|metadata| metadata.world_name);
+
+    match world_name {
+        Some(world_name) => format!("{} - Uploaded via Send to VRC", world_name),
+        None => "Uploaded via Send to VRC".to_string(),
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, specta::Type)]
+#[serde(tag = "type", content = "reason")]
+pub enum BatchPrintStatus {
+    Printed,
+    Skipped(String),
+    Failed(AppError),
+}
+
+#[derive(Debug, Clone, serde::Serialize, specta::Type)]
+pub struct BatchPrintResult {
+    file: String,
+    status: BatchPrintStatus,
+}
+
+#[derive(Debug, Clone, serde::Serialize, specta::Type, tauri_specta::Event)]
+pub struct BatchPrintProgressEvent {
+    index: u32,
+    total: u32,
+    result: BatchPrintResult,
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn upload_images_to_vrchat_print(
+    app_handle: tauri::AppHandle,
+    file_paths: Vec<String>,
+) -> Result<Vec<BatchPrintResult>, AppError> {
+    let vrchat_api_key =
+        read_vrchat_api_key(&app_handle.state::<crate::app_data::AppData>().lock_config())?;
+
+    let queue_callback = Some(crate::vrchat_queue::create_queue_callback(&app_handle));
+
+    let total = file_paths.len() as u32;
+
+    // NOTE: A workspace per batch, so batches running at the same time do not share letterboxed images.
+    let workspace = crate::jobs::create_workspace()?;
+
+    let results = print_files_in_batch(
+        &file_paths,
+        |index, file_path| {
+            let vrchat_api_key = vrchat_api_key.clone();
+            let queue_callback = queue_callback.as_ref();
+            let letterboxed_image_path = workspace.join(format!("letterboxed_image_{}.png", index));
+
+            async move {
+                upload_image_to_vrchat_print_internal(
+                    &file_path,
+                    &vrchat_api_key,
+                    &letterboxed_image_path,
+                    queue_callback,
+                )
+                .await
+            }
+        },
+        |index, result| {
+            BatchPrintProgressEvent {
+                index: index as u32,
+                total,
+                result: result.clone(),
+            }
+            .emit(&app_handle)
+            .unwrap_or_else(|e| debug!("Failed to emit batch print progress event: {:?}", e));
+        },
+    )
+    .await;
+
+    crate::jobs::remove_workspace(&workspace);
+
+    Ok(results)
+}
+
+// NOTE: Files are downloaded and validated right before their turn so that a long batch does not hold every decoded image in memory.
+async fn print_files_in_batch<F, Fut>(
+    file_paths: &[String],
+    mut print: F,
+    mut on_result: impl FnMut(usize, &BatchPrintResult),
+) -> Vec<BatchPrintResult>
+where
+    F: FnMut(usize, String) -> Fut,
+    Fut: std::future::Future<Output = Result<(), AppError>>,
+{
+    let mut results: Vec<BatchPrintResult> = Vec::with_capacity(file_paths.len());
+    let mut printed_files = std::collections::HashSet::new();
+    let mut aborted_by: Option<String> = None;
+
+    for (index, file_path) in file_paths.iter().enumerate() {
+        // URLs are compared as they are, before downloading them
+        let file_key = if crate::remote_image::is_remote_url(file_path) {
+            Some(std::path::PathBuf::from(file_path))
+        } else {
+            std::fs::canonicalize(file_path).ok()
+        };
+
+        let status = if let Some(reason) = &aborted_by {
+            BatchPrintStatus::Skipped(reason.clone())
+        } else if file_key.is_none() {
+            BatchPrintStatus::Skipped("File does not exist".to_string())
+        } else if !printed_files.insert(file_key) {
+            BatchPrintStatus::Skipped("Duplicate file in batch".to_string())
+        } else {
+            match crate::remote_image::resolve_input(file_path).await {
+                Err(error) => BatchPrintStatus::Failed(error),
+                Ok(input) if image::image_dimensions(input.path()).is_err() => {
+                    BatchPrintStatus::Skipped("File is not a readable image".to_string())
+                }
+                Ok(input) => match print(index, input.path().to_string()).await {
+                    Ok(()) => BatchPrintStatus::Printed,
+                    Err(error) => {
+                        // NOTE: Every remaining file would fail for the same reason.
+                        if matches!(
+                            error,
+                            AppError::VrchatAuthRequired(_) | AppError::VrchatPlusRequired(_)
+                        ) {
+                            aborted_by = Some(format!("Aborted after previous error: {}", error));
+                        }
+
+                        BatchPrintStatus::Failed(error)
+                    }
+                },
+            }
+        };
+
+        let result = BatchPrintResult {
+            file: file_path.clone(),
+            status,
+        };
+
+        on_result(index, &result);
+
+        results.push(result);
+    }
+
+    results
+}
+
+pub fn read_vrchat_api_key(config: &crate::config::Config) -> Result<String, AppError> {
+    let Some(vrchat_api_key) = config.read_vrchat_api_key()? else {
         return Err(AppError::VrchatAuthRequired(
             "VRChat API key is not set in configuration".to_string(),
         ));
@@ -26,21 +221,11 @@ pub async fn upload_image_to_vrchat_print(
         ));
     };
 
-    let path = std::path::Path::new(file_path);
-
-    if !path.exists() {
-        return Err(AppError::Unknown("File does not exist".to_string()));
-    }
-
-    let letterboxed_image_path = crate::file::temp_file_path("letterboxed_image.png");
-
-    resize_image_vrchat_print(file_path, &letterboxed_image_path.to_string_lossy())?;
-
-    send_file_to_print(&letterboxed_image_path, vrchat_api_key).await
+    Ok(vrchat_api_key)
 }
 
 // NOTE: VRChat print uploading requires a png image that sized 2048x1440, and its main image scaled to be 1920x1080 and placed at (64,96).
-fn resize_image_vrchat_print(image_path: &str, output_path: &str) -> Result<(), AppError> {
+pub fn resize_image_vrchat_print(image_path: &str, output_path: &str) -> Result<(), AppError> {
     let whole_width = 2048;
     let whole_height = 1440;
     let width = 1920;
@@ -92,7 +277,8 @@ fn resize_image_vrchat_print(image_path: &str, output_path: &str) -> Result<(),
 async fn send_file_to_print(
     file_path: &std::path::Path,
     vrchat_api_key: String,
-) -> Result<(), AppError> {
+    note: &str,
+) -> Result<(), VrchatRequestError> {
     let bytes = std::fs::read(file_path).map_err(AppError::from_error_with_message(
         "failed to get file bytes",
     ))?;
@@ -115,7 +301,7 @@ async fn send_file_to_print(
             "timestamp",
             format!("{}", chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ")),
         )
-        .text("note", "Uploaded via Send to VRC");
+        .text("note", note.to_string());
 
     debug!("Form: {:?}", form);
 
@@ -130,25 +316,30 @@ async fn send_file_to_print(
 
     debug!("Response: {:?}", response);
 
+    if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(VrchatRequestError::RateLimited(
+            crate::vrchat_queue::parse_retry_after(response.headers()),
+        ));
+    }
+
     if response.status() == reqwest::StatusCode::UNAUTHORIZED {
         return Err(AppError::VrchatAuthRequired(
             "VRChat API key is invalid or expired".to_string(),
-        ));
+        )
+        .into());
     }
 
     if response.status() == reqwest::StatusCode::FORBIDDEN {
         return Err(AppError::VrchatPlusRequired(
             "VRChat Plus subscription is required to use VRChat Print".to_string(),
-        ));
+        )
+        .into());
     }
 
     if !response.status().is_success() {
         let text = response.text().await;
         // log::debug!("Response text: {:?}", text);
-        return Err(AppError::Unknown(format!(
-            "Failed to upload file: {:?}",
-            text
-        )));
+        return Err(AppError::Unknown(format!("Failed to upload file: {:?}", text)).into());
     }
 
     info!("File uploaded successfully.");
@@ -284,6 +475,12 @@ pub async fn login_to_vrchat_internal(
             "Failed to send login request",
         ))?;
 
+    if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(AppError::VrchatRateLimited(
+            "Too many login attempts. Please wait and try again".to_string(),
+        ));
+    }
+
     if response.status() != reqwest::StatusCode::OK {
         return Err(AppError::Unknown(format!(
             "Login failed with status code: {}",
@@ -516,6 +713,162 @@ pub async fn logout_from_vrchat(app_handle: tauri::AppHandle) -> Result<(), AppE
 
 #[cfg(test)]
 mod tests {
+    fn test_image_path() -> String {
+        std::env::var("CARGO_MANIFEST_DIR").unwrap() + "/test_data/input_image.png"
+    }
+
+    fn copied_test_image_path(name: &str) -> String {
+        let path = std::env::temp_dir().join(format!("send_to_vrc_test_{}.png", name));
+        std::fs::copy(test_image_path(), &path).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[tokio::test]
+    async fn batch_print_reports_result_per_file() {
+        let files = vec![
+            test_image_path(),
+            std::env::var("CARGO_MANIFEST_DIR").unwrap() + "/test_data/missing.png",
+            std::env::var("CARGO_MANIFEST_DIR").unwrap() + "/Cargo.toml",
+            test_image_path(),
+        ];
+
+        let mut progress = Vec::new();
+
+        let results = super::print_files_in_batch(
+            &files,
+            |_, _| async { Ok(()) },
+            |index, _| progress.push(index),
+        )
+        .await;
+
+        assert!(matches!(
+            results[0].status,
+            super::BatchPrintStatus::Printed
+        ));
+        assert!(matches!(
+            results[1].status,
+            super::BatchPrintStatus::Skipped(_)
+        ));
+        assert!(matches!(
+            results[2].status,
+            super::BatchPrintStatus::Skipped(_)
+        ));
+        assert!(matches!(
+            results[3].status,
+            super::BatchPrintStatus::Skipped(_)
+        ));
+        assert_eq!(progress, vec![0, 1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn batch_print_continues_after_failure() {
+        let files = vec![
+            test_image_path(),
+            copied_test_image_path("batch_print_continues_after_failure"),
+        ];
+
+        let results = super::print_files_in_batch(
+            &files,
+            |index, _| async move {
+                if index == 0 {
+                    Err(crate::error::AppError::VrchatRateLimited(
+                        "VRChat API rate limit exceeded".to_string(),
+                    ))
+                } else {
+                    Ok(())
+                }
+            },
+            |_, _| {},
+        )
+        .await;
+
+        assert!(matches!(
+            results[0].status,
+            super::BatchPrintStatus::Failed(crate::error::AppError::VrchatRateLimited(_))
+        ));
+        assert!(matches!(
+            results[1].status,
+            super::BatchPrintStatus::Printed
+        ));
+    }
+
+    #[tokio::test]
+    async fn batch_print_aborts_after_auth_failure() {
+        let files = vec![
+            test_image_path(),
+            copied_test_image_path("batch_print_aborts_after_auth_failure"),
+        ];
+
+        let mut printed = 0;
+
+        let results = super::print_files_in_batch(
+            &files,
+            |_, _| {
+                printed += 1;
+                async {
+                    Err(crate::error::AppError::VrchatAuthRequired(
+                        "VRChat API key is invalid or expired".to_string(),
+                    ))
+                }
+            },
+            |_, _| {},
+        )
+        .await;
+
+        assert_eq!(printed, 1);
+        assert!(matches!(
+            results[0].status,
+            super::BatchPrintStatus::Failed(_)
+        ));
+        assert!(matches!(
+            results[1].status,
+            super::BatchPrintStatus::Skipped(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn batch_print_downloads_urls() {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!(
+            "http://127.0.0.1:{}/a.png",
+            server.server_addr().to_ip().unwrap().port()
+        );
+
+        std::thread::spawn(move || {
+            let request = server.recv().unwrap();
+            let response =
+                tiny_http::Response::from_data(std::fs::read(test_image_path()).unwrap())
+                    .with_header(
+                        tiny_http::Header::from_bytes("Content-Type", "image/png").unwrap(),
+                    );
+            request.respond(response).unwrap();
+        });
+
+        let mut downloaded_files = Vec::new();
+
+        let results = super::print_files_in_batch(
+            &[url.clone(), url],
+            |_, file_path| {
+                assert!(image::open(&file_path).is_ok());
+                downloaded_files.push(file_path);
+                async { Ok(()) }
+            },
+            |_, _| {},
+        )
+        .await;
+
+        assert!(matches!(
+            results[0].status,
+            super::BatchPrintStatus::Printed
+        ));
+        assert!(matches!(
+            results[1].status,
+            super::BatchPrintStatus::Skipped(_)
+        ));
+        assert_eq!(downloaded_files.len(), 1);
+        assert!(!std::path::Path::new(&downloaded_files[0]).exists());
+    }
+
     #[tokio::test]
     #[ignore]
     async fn login_with_totp() {
@@ -0,0 +1,379 @@
+use crate::prelude::*;
+use tauri_specta::Event as _;
+
+// NOTE: VRChat does not publish its rate limits. These values are conservative enough for prints.
+const DEFAULT_MIN_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+const DEFAULT_RETRY_AFTER: std::time::Duration = std::time::Duration::from_secs(60);
+const DEFAULT_MAX_RETRIES: u32 = 3;
+
+static VRCHAT_REQUEST_QUEUE: std::sync::OnceLock<RequestQueue> = std::sync::OnceLock::new();
+
+pub fn vrchat_request_queue() -> &'static RequestQueue {
+    VRCHAT_REQUEST_QUEUE
+        .get_or_init(|| RequestQueue::new(DEFAULT_MIN_INTERVAL, DEFAULT_MAX_RETRIES))
+}
+
+#[derive(Debug)]
+pub enum VrchatRequestError {
+    RateLimited(Option<std::time::Duration>),
+    Other(AppError),
+}
+
+impl From<AppError> for VrchatRequestError {
+    fn from(error: AppError) -> Self {
+        VrchatRequestError::Other(error)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(tag = "type", content = "content")]
+pub enum VrchatQueueStatus {
+    // Number of requests ahead of this one, including the one being sent.
+    Queued(u32),
+    WaitingForRateLimit(u32),
+    Sending,
+}
+
+#[derive(Debug, Clone, serde::Serialize, specta::Type, tauri_specta::Event)]
+pub struct VrchatQueueEvent {
+    status: VrchatQueueStatus,
+}
+
+pub type QueueCallback = Box<dyn Fn(VrchatQueueStatus) + Send + Sync + 'static>;
+
+pub fn create_queue_callback(handle: &tauri::AppHandle) -> QueueCallback {
+    let handle = handle.clone();
+    Box::new(move |status| {
+        VrchatQueueEvent { status }
+            .emit(&handle)
+            .unwrap_or_else(|e| debug!("Failed to emit VRChat queue event: {:?}", e));
+    })
+}
+
+pub struct RequestQueue {
+    min_interval: std::time::Duration,
+    max_retries: u32,
+    // Holds the earliest instant the next request may be sent. Tokio's mutex is fair, so this is also the queue order.
+    next_slot: tokio::sync::Mutex<Option<tokio::time::Instant>>,
+    tickets: std::sync::Mutex<std::collections::BTreeSet<u64>>,
+    next_ticket: std::sync::atomic::AtomicU64,
+    tickets_changed: tokio::sync::watch::Sender<u64>,
+}
+
+struct Ticket<'a> {
+    queue: &'a RequestQueue,
+    id: u64,
+}
+
+impl Drop for Ticket<'_> {
+    fn drop(&mut self) {
+        self.queue.tickets.lock().unwrap().remove(&self.id);
+        self.queue
+            .tickets_changed
+            .send_modify(|version| *version = version.wrapping_add(1));
+    }
+}
+
+impl RequestQueue {
+    pub fn new(min_interval: std::time::Duration, max_retries: u32) -> Self {
+        Self {
+            min_interval,
+            max_retries,
+            next_slot: tokio::sync::Mutex::new(None),
+            tickets: std::sync::Mutex::new(std::collections::BTreeSet::new()),
+            next_ticket: std::sync::atomic::AtomicU64::new(0),
+            tickets_changed: tokio::sync::watch::Sender::new(0),
+        }
+    }
+
+    pub fn pending_requests(&self) -> usize {
+        self.tickets.lock().unwrap().len()
+    }
+
+    fn take_ticket(&self) -> Ticket<'_> {
+        let id = self
+            .next_ticket
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+
+        self.tickets.lock().unwrap().insert(id);
+
+        Ticket { queue: self, id }
+    }
+
+    fn position(&self, ticket: &Ticket) -> u32 {
+        self.tickets.lock().unwrap().range(..ticket.id).count() as u32
+    }
+
+    /// Runs `request` once every request queued before it has finished, keeping at least `min_interval` between
+    /// requests. Requests rejected with `VrchatRequestError::RateLimited` are retried after `Retry-After`.
+    pub async fn submit<T, F, Fut>(
+        &self,
+        status_callback: Option<&QueueCallback>,
+        mut request: F,
+    ) -> Result<T, AppError>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T, VrchatRequestError>>,
+    {
+        let notify = |status: VrchatQueueStatus| {
+            if let Some(cb) = status_callback {
+                cb(status)
+            }
+        };
+
+        let ticket = self.take_ticket();
+        let mut tickets_changed = self.tickets_changed.subscribe();
+
+        let lock = self.next_slot.lock();
+        tokio::pin!(lock);
+
+        let mut next_slot = loop {
+            notify(VrchatQueueStatus::Queued(self.position(&ticket)));
+
+            tokio::select! {
+                next_slot = &mut lock => break next_slot,
+                _ = tickets_changed.changed() => continue,
+            }
+        };
+
+        let mut retries = 0;
+
+        loop {
+            if let Some(slot) = *next_slot {
+                let now = tokio::time::Instant::now();
+
+                if slot > now {
+                    notify(VrchatQueueStatus::WaitingForRateLimit(
+                        (slot - now).as_secs_f32().ceil() as u32,
+                    ));
+                    tokio::time::sleep_until(slot).await;
+                }
+            }
+
+            notify(VrchatQueueStatus::Sending);
+
+            let result = request().await;
+
+            *next_slot = Some(tokio::time::Instant::now() + self.min_interval);
+
+            let retry_after = match result {
+                Ok(value) => return Ok(value),
+                Err(VrchatRequestError::Other(error)) => return Err(error),
+                Err(VrchatRequestError::RateLimited(retry_after)) => {
+                    retry_after.unwrap_or(DEFAULT_RETRY_AFTER)
+                }
+            };
+
+            *next_slot = Some(tokio::time::Instant::now() + retry_after.max(self.min_interval));
+
+            if retries >= self.max_retries {
+                return Err(AppError::VrchatRateLimited(format!(
+                    "VRChat API rate limit exceeded. Retry after {} seconds",
+                    retry_after.as_secs()
+                )));
+            }
+
+            retries += 1;
+
+            warn!(
+                "VRChat API rate limit exceeded. Retrying in {:?} ({}/{})",
+                retry_after, retries, self.max_retries
+            );
+        }
+    }
+}
+
+/// Parses `Retry-After` header, which is either delay seconds or an HTTP date.
+pub fn parse_retry_after(headers: &reqwest::header::HeaderMap) -> Option<std::time::Duration> {
+    let value = headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(std::time::Duration::from_secs(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+
+    Some(
+        (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers_with_retry_after(value: &str) -> reqwest::header::HeaderMap {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(reqwest::header::RETRY_AFTER, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn parses_retry_after_seconds() {
+        assert_eq!(
+            parse_retry_after(&headers_with_retry_after("120")),
+            Some(std::time::Duration::from_secs(120))
+        );
+    }
+
+    #[test]
+    fn parses_retry_after_date() {
+        let date = (chrono::Utc::now() + chrono::Duration::seconds(30))
+            .format("%a, %d %b %Y %H:%M:%S GMT")
+            .to_string();
+
+        let retry_after = parse_retry_after(&headers_with_retry_after(&date)).unwrap();
+
+        assert!(retry_after <= std::time::Duration::from_secs(30));
+        assert!(retry_after >= std::time::Duration::from_secs(28));
+    }
+
+    #[test]
+    fn parses_retry_after_in_the_past_as_zero() {
+        assert_eq!(
+            parse_retry_after(&headers_with_retry_after("Wed, 21 Oct 2015 07:28:00 GMT")),
+            Some(std::time::Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn ignores_missing_or_invalid_retry_after() {
+        assert_eq!(parse_retry_after(&reqwest::header::HeaderMap::new()), None);
+        assert_eq!(parse_retry_after(&headers_with_retry_after("soon")), None);
+    }
+
+    #[tokio::test]
+    async fn paces_requests() {
+        let queue = RequestQueue::new(std::time::Duration::from_millis(200), 0);
+        let started = std::time::Instant::now();
+
+        for _ in 0..3 {
+            queue
+                .submit(None, || async { Ok::<_, VrchatRequestError>(()) })
+                .await
+                .unwrap();
+        }
+
+        assert!(started.elapsed() >= std::time::Duration::from_millis(400));
+    }
+
+    #[tokio::test]
+    async fn retries_after_rate_limit() {
+        let queue = RequestQueue::new(std::time::Duration::from_millis(10), 3);
+        let attempts = std::sync::atomic::AtomicU32::new(0);
+        let started = std::time::Instant::now();
+
+        let result = queue
+            .submit(None, || async {
+                if attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 0 {
+                    Err(VrchatRequestError::RateLimited(Some(
+                        std::time::Duration::from_millis(300),
+                    )))
+                } else {
+                    Ok("printed")
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), "printed");
+        assert_eq!(attempts.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert!(started.elapsed() >= std::time::Duration::from_millis(300));
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_retries() {
+        let queue = RequestQueue::new(std::time::Duration::from_millis(1), 1);
+
+        let result = queue
+            .submit(None, || async {
+                Err::<(), _>(VrchatRequestError::RateLimited(Some(
+                    std::time::Duration::from_millis(1),
+                )))
+            })
+            .await;
+
+        assert!(matches!(result, Err(AppError::VrchatRateLimited(_))));
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_other_errors() {
+        let queue = RequestQueue::new(std::time::Duration::from_millis(1), 3);
+        let attempts = std::sync::atomic::AtomicU32::new(0);
+
+        let result = queue
+            .submit(None, || async {
+                attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                Err::<(), _>(VrchatRequestError::Other(AppError::VrchatPlusRequired(
+                    "VRChat Plus subscription is required".to_string(),
+                )))
+            })
+            .await;
+
+        assert!(matches!(result, Err(AppError::VrchatPlusRequired(_))));
+        assert_eq!(attempts.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn reports_queue_position() {
+        let queue = std::sync::Arc::new(RequestQueue::new(std::time::Duration::ZERO, 0));
+        let (release_tx, release_rx) = tokio::sync::oneshot::channel::<()>();
+        let release_rx = std::sync::Mutex::new(Some(release_rx));
+
+        let first = {
+            let queue = queue.clone();
+            tokio::spawn(async move {
+                queue
+                    .submit(None, || {
+                        let release_rx = release_rx.lock().unwrap().take();
+                        async move {
+                            if let Some(rx) = release_rx {
+                                rx.await.ok();
+                            }
+                            Ok::<_, VrchatRequestError>(())
+                        }
+                    })
+                    .await
+            })
+        };
+
+        while queue.pending_requests() < 1 {
+            tokio::task::yield_now().await;
+        }
+
+        let statuses = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let callback: QueueCallback = {
+            let statuses = statuses.clone();
+            Box::new(move |status| statuses.lock().unwrap().push(status))
+        };
+
+        let second = {
+            let queue = queue.clone();
+            tokio::spawn(async move {
+                queue
+                    .submit(Some(&callback), || async {
+                        Ok::<_, VrchatRequestError>(())
+                    })
+                    .await
+            })
+        };
+
+        while queue.pending_requests() < 2 {
+            tokio::task::yield_now().await;
+        }
+
+        release_tx.send(()).unwrap();
+
+        first.await.unwrap().unwrap();
+        second.await.unwrap().unwrap();
+
+        let statuses = statuses.lock().unwrap();
+        assert_eq!(statuses.first(), Some(&VrchatQueueStatus::Queued(1)));
+        assert_eq!(statuses.last(), Some(&VrchatQueueStatus::Sending));
+    }
+}
@@ -2,38 +2,30 @@ import { useSetAtom } from "jotai";
 import { useEffect } from "react";
 import { RouterProvider } from "@tanstack/react-router";
 import { listen } from "@tauri-apps/api/event";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 import { events } from "./bindings.gen";
-import {
-  sendImageToImageViewerAtom,
-  sendImageToVRChatPrintAtom,
-  sendImageToVideoPlayerAtom,
-  setFileToSendAtom,
-} from "./stores/atoms";
+import { useLocalized } from "./i18n";
+import { queueSendRequestsAtom, setFileToSendAtom } from "./stores/atoms";
 import router from "./stores/router";
 
 type SendRequestEvent = string;
 
 function App() {
   const setFileToSend = useSetAtom(setFileToSendAtom);
-  const sendImageToVideoPlayer = useSetAtom(sendImageToVideoPlayerAtom);
-  const sendImageToImageViewer = useSetAtom(sendImageToImageViewerAtom);
-  const sendImageToVRChatPrint = useSetAtom(sendImageToVRChatPrintAtom);
+  const queueSendRequests = useSetAtom(queueSendRequestsAtom);
+  const localized = useLocalized();
 
   useEffect(() => {
     events.sendRequestEvent.listen((event) => {
       console.log(event);
-      setFileToSend(event.payload.file);
-      switch (event.payload.mode) {
-        case "UploadImageToVideoServer":
-          sendImageToVideoPlayer(event.payload.file);
-          break;
-        case "UploadImageToImageServer":
-          sendImageToImageViewer(event.payload.file);
-          break;
-        case "UploadImageToVRChatPrint":
-          sendImageToVRChatPrint(event.payload.file);
-          break;
-      }
+      queueSendRequests([
+        {
+          file: event.payload.file,
+          mode: event.payload.mode,
+          preselectedMode: event.payload.preselected_mode ?? null,
+          copy: event.payload.copy ?? null,
+        },
+      ]);
     });
     const listener = listen("send_request", (event) => {
       const payload = event.payload as SendRequestEvent;
@@ -48,12 +40,28 @@ function App() {
           console.error("Failed to listen to send_request events:", e);
         });
     };
-  }, [
-    sendImageToImageViewer,
-    sendImageToVRChatPrint,
-    sendImageToVideoPlayer,
-    setFileToSend,
-  ]);
+  }, [queueSendRequests, setFileToSend]);
+
+  useEffect(() => {
+    // Capture overlays render the app too, so only the main window reports failures
+    if (getCurrentWebviewWindow().label !== "main") {
+      return;
+    }
+
+    const listener = events.captureFailedEvent.listen((event) => {
+      console.error(event.payload);
+      alert(`${localized("capture.failed")}: ${event.payload.message}`);
+    });
+
+    return () => {
+      listener
+        .then((unlisten) => unlisten && unlisten())
+        .catch((e) => {
+          console.error("Failed to listen to capture failed events:", e);
+        });
+    };
+  }, [localized]);
+
   return <RouterProvider router={router} />;
 }
 
@@ -47,6 +47,17 @@ async isAbleToReadImageFile(filePath: string) : Promise<Result<boolean, null>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Reads world and player information that VRChat (XMP) or VRCX (JSON description) embedded in a PNG screenshot.
+ */
+async readScreenshotMetadata(filePath: string) : Promise<Result<ScreenshotMetadata | null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("read_screenshot_metadata", { filePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async uploadImageToVideoServer(filePath: string, apiKey: string, uploaderBaseUrl: string) : Promise<Result<string, AppError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("upload_image_to_video_server", { filePath, apiKey, uploaderBaseUrl }) };
@@ -71,6 +82,25 @@ async uploadImageToVrchatPrint(filePath: string) : Promise<Result<null, AppError
     else return { status: "error", error: e  as any };
 }
 },
+async uploadImagesToVrchatPrint(filePaths: string[]) : Promise<Result<BatchPrintResult[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("upload_images_to_vrchat_print", { filePaths }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Renders the image exactly as it would be uploaded for `mode` and returns the path to the rendered PNG.
+ */
+async renderSendPreview(filePath: string, mode: SendRequestEventMode) : Promise<Result<string, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("render_send_preview", { filePath, mode }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async loginToVrchat(username: string, password: string) : Promise<Result<LoginResult, AppError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("login_to_vrchat", { username, password }) };
@@ -135,6 +165,17 @@ async startCapture() : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Starts the capture after a countdown. Uses `capture_delay_seconds` from the config when no delay is given.
+ */
+async startDelayedCapture(delaySeconds: number | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_delayed_capture", { delaySeconds }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async stopCapture() : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("stop_capture") };
@@ -143,9 +184,20 @@ async stopCapture() : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
-async finishCaptureWithCroppedRect(monitorId: string, rect: NormalizedRect, nextAction: FinishCaptureNextAction) : Promise<Result<null, string>> {
+async getCaptureState() : Promise<CaptureState> {
+    return await TAURI_INVOKE("get_capture_state");
+},
+async finishCaptureWithCroppedRect(monitorId: string, rect: NormalizedRect, nextAction: FinishCaptureNextAction, annotations: Annotation[] | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("finish_capture_with_cropped_rect", { monitorId, rect, nextAction, annotations }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async finishCaptureWithDesktopRect(rect: DesktopRect, nextAction: FinishCaptureNextAction, annotations: Annotation[] | null) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("finish_capture_with_cropped_rect", { monitorId, rect, nextAction }) };
+    return { status: "ok", data: await TAURI_INVOKE("finish_capture_with_desktop_rect", { rect, nextAction, annotations }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -159,6 +211,60 @@ async getCaptureUrlCommand(monitorId: string) : Promise<Result<string, string>>
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Captures the last selected region again without showing the overlays, and sends it the same way.
+ */
+async repeatLastCapture() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("repeat_last_capture") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listCaptureWindows() : Promise<Result<CaptureWindow[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_capture_windows") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Captures one window without the crop overlay and asks to send it.
+ */
+async captureWindow(windowId: string, excludeTitleBar: boolean, nextAction: FinishCaptureNextAction) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("capture_window", { windowId, excludeTitleBar, nextAction }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async captureVrchatWindow(excludeTitleBar: boolean, nextAction: FinishCaptureNextAction) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("capture_vrchat_window", { excludeTitleBar, nextAction }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Re-captures `source` every `interval_seconds` and serves the latest frame on the LAN as a video,
+ * since only video players in VRChat load arbitrary URLs. Returns the URL, which stays the same
+ * while the board runs, for video players to refresh.
+ */
+async startLiveBoard(source: LiveBoardSource, intervalSeconds: number) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_live_board", { source, intervalSeconds }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async stopLiveBoard() : Promise<void> {
+    await TAURI_INVOKE("stop_live_board");
+},
 async configFilePath() : Promise<Result<string, AppError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("config_file_path") };
@@ -182,6 +288,39 @@ async getLaunchOptions() : Promise<Result<LaunchOptions, AppError>> {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
+},
+async defaultScreenshotDirectory() : Promise<Result<string, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("default_screenshot_directory") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async restartOscListener() : Promise<void> {
+    await TAURI_INVOKE("restart_osc_listener");
+},
+async restartControlApi() : Promise<void> {
+    await TAURI_INVOKE("restart_control_api");
+},
+/**
+ * Registers the hotkeys in the config again, replacing the previous ones. Call after changing them.
+ */
+async registerHotkeys() : Promise<Result<HotkeyStatus[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("register_hotkeys") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async sendClipboardImage(mode: SendRequestEventMode) : Promise<Result<string | null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("send_clipboard_image", { mode }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
 }
 }
 
@@ -189,9 +328,27 @@ async getLaunchOptions() : Promise<Result<LaunchOptions, AppError>> {
 
 
 export const events = __makeEvents__<{
-sendRequestEvent: SendRequestEvent
+batchPrintProgressEvent: BatchPrintProgressEvent,
+captureCountdownEvent: CaptureCountdownEvent,
+captureFailedEvent: CaptureFailedEvent,
+captureStateEvent: CaptureStateEvent,
+clipboardImageDetectedEvent: ClipboardImageDetectedEvent,
+liveBoardStatusEvent: LiveBoardStatusEvent,
+screenshotDetectedEvent: ScreenshotDetectedEvent,
+sendRequestEvent: SendRequestEvent,
+sendResultEvent: SendResultEvent,
+vrchatQueueEvent: VrchatQueueEvent
 }>({
-sendRequestEvent: "send-request-event"
+batchPrintProgressEvent: "batch-print-progress-event",
+captureCountdownEvent: "capture-countdown-event",
+captureFailedEvent: "capture-failed-event",
+captureStateEvent: "capture-state-event",
+clipboardImageDetectedEvent: "clipboard-image-detected-event",
+liveBoardStatusEvent: "live-board-status-event",
+screenshotDetectedEvent: "screenshot-detected-event",
+sendRequestEvent: "send-request-event",
+sendResultEvent: "send-result-event",
+vrchatQueueEvent: "vrchat-queue-event"
 })
 
 /** user-defined constants **/
@@ -200,16 +357,128 @@ sendRequestEvent: "send-request-event"
 
 /** user-defined types **/
 
-export type AppError = { type: "ConfigContents"; message: string } | { type: "ConfigExistance"; message: string } | { type: "ConfigDirectoryExistance"; message: string } | { type: "UploaderAuthRequired"; message: string } | { type: "VrchatAuthRequired"; message: string } | { type: "VrchatPlusRequired"; message: string } | { type: "Unknown"; message: string }
+/**
+ * Shape drawn on the cropped image before it is sent. Shapes are drawn in order,
+ * so a blur box covers the shapes before it.
+ */
+export type Annotation = { Arrow: { from: Point; to: Point; color: AnnotationColor; width: number } } | 
+/**
+ * Outlined with `stroke_width`, or filled without it, e.g. with a translucent
+ * color to highlight text.
+ */
+{ Rectangle: { rect: AnnotationRect; color: AnnotationColor; stroke_width: number | null } } | { Freehand: { points: Point[]; color: AnnotationColor; width: number } } | 
+/**
+ * Label of `size` pixel tall text drawn at `position` (top left). Characters the
+ * font does not have are drawn as boxes.
+ */
+{ Text: { position: Point; text: string; color: AnnotationColor; size: number; background: AnnotationColor | null } } | { Blur: { rect: AnnotationRect; radius: number } }
+export type AnnotationColor = { r: number; g: number; b: number; a: number }
+/**
+ * Rectangle in pixels of the cropped image.
+ */
+export type AnnotationRect = { x: number; y: number; width: number; height: number }
+export type AppError = { type: "ConfigContents"; message: string } | { type: "ConfigExistance"; message: string } | { type: "ConfigDirectoryExistance"; message: string } | { type: "UploaderAuthRequired"; message: string } | { type: "VrchatAuthRequired"; message: string } | { type: "VrchatPlusRequired"; message: string } | { type: "VrchatRateLimited"; message: string } | { type: "Unknown"; message: string }
+export type BatchPrintProgressEvent = { index: number; total: number; result: BatchPrintResult }
+export type BatchPrintResult = { file: string; status: BatchPrintStatus }
+export type BatchPrintStatus = { type: "Printed" } | { type: "Skipped"; reason: string } | { type: "Failed"; reason: AppError }
+export type CaptureCountdownEvent = { remaining_seconds: number }
+export type CaptureFailedEvent = { 
+/**
+ * State the capture was in when it failed.
+ */
+stage: CaptureState; message: string }
+export type CaptureState = "Idle" | 
+/**
+ * Monitors are being captured.
+ */
+"Capturing" | 
+/**
+ * Overlays are shown for the user to select a region.
+ */
+"Selecting" | 
+/**
+ * The selection is being cropped.
+ */
+"Finishing"
+export type CaptureStateEvent = { state: CaptureState }
+export type CaptureWindow = { 
+/**
+ * Native handle as a string, because TypeScript numbers cannot hold 64-bit handles.
+ */
+id: string; title: string; process_name: string; width: number; height: number }
+export type ClipboardImageDetectedEvent = { file: string; auto_send: boolean }
+/**
+ * Selection in virtual desktop pixels, which may span several monitors.
+ */
+export type DesktopRect = { x: number; y: number; width: number; height: number }
 export type FinishCaptureNextAction = "Ask" | "UploadImageToVideoServer" | "UploadImageToImageServer" | "UploadImageToVRChatPrint"
+export type HotkeyAction = "CaptureRegion" | "CaptureRegionToVideoServer" | "RepeatLastRegion" | "SendClipboardImage"
+export type HotkeyStatus = { action: HotkeyAction; shortcut: string; 
+/**
+ * Why the hotkey is not registered, `None` if it is.
+ */
+error: string | null }
 export type LaunchOptions = { mode: LaunchOptionsMode }
-export type LaunchOptionsMode = { type: "Default" } | { type: "Send"; args: { file: string } } | { type: "Capture" }
+export type LaunchOptionsMode = { type: "Default" } | { type: "Send"; args: { files: string[]; options: SendOptions } } | { type: "Capture" } | { type: "Help" } | { type: "Version" }
+export type LiveBoardSource = 
+/**
+ * The region selected in the last capture.
+ */
+"LastRegion" | { Window: { window_id: string; exclude_title_bar: boolean } }
+export type LiveBoardStatusEvent = { running: boolean; url: string | null; published_frames: number; skipped_frames: number; 
+/**
+ * Error of the last capture, cleared by the next successful one.
+ */
+error: string | null }
 export type LoginResult = { type: "Success" } | { type: "RequiresTwoFactorAuth"; content: TwoFactorMethod[] }
 export type NormalizedRect = { x1: number; y1: number; x2: number; y2: number }
-export type SendRequestEvent = { file: string; mode: SendRequestEventMode | null }
+/**
+ * Point in pixels of the cropped image.
+ */
+export type Point = { x: number; y: number }
+export type ScreenshotDetectedEvent = { file: string; auto_send: boolean }
+export type ScreenshotMetadata = { world_name: string | null; world_id: string | null; instance_id: string | null; author: string | null; players: string[] }
+export type SendOptions = { mode: SendRequestEventMode | null; 
+/**
+ * Highlighted on the send page until the user confirms it. Deep links use this instead of
+ * `mode`, because any web page can open them.
+ */
+preselected_mode: SendRequestEventMode | null; 
+/**
+ * Overrides `should_copy_after_upload` when set.
+ */
+copy: boolean | null; 
+/**
+ * Applied by the backend at startup, see `config::set_profile`.
+ */
+profile: string | null; 
+/**
+ * Handled by `headless` before Tauri starts, so the frontend never sees it set.
+ */
+no_gui: boolean; json: boolean }
+export type SendOutcome = { type: "Sent"; content: string | null } | { type: "Failed"; content: AppError }
+export type SendRequestEvent = { file: string; mode: SendRequestEventMode | null; 
+/**
+ * Highlighted instead of sent right away, see `SendOptions::preselected_mode`.
+ */
+preselected_mode?: SendRequestEventMode | null; 
+/**
+ * Overrides `should_copy_after_upload` when set.
+ */
+copy?: boolean | null; 
+/**
+ * Copies kept by capture auto-save.
+ */
+saved_files?: string[] }
 export type SendRequestEventMode = "UploadImageToVideoServer" | "UploadImageToImageServer" | "UploadImageToVRChatPrint"
+/**
+ * Reports the result of a send that was started by the backend (e.g. folder watcher), not by the frontend.
+ */
+export type SendResultEvent = { file: string; mode: SendRequestEventMode; outcome: SendOutcome }
 export type Tos = { version: number; content: string }
 export type TwoFactorMethod = "Totp" | "EmailOtp" | { Unknown: string }
+export type VrchatQueueEvent = { status: VrchatQueueStatus }
+export type VrchatQueueStatus = { type: "Queued"; content: number } | { type: "WaitingForRateLimit"; content: number } | { type: "Sending" }
 
 /** tauri-specta globals **/
 
@@ -7,13 +7,15 @@ type ButtonCardProps = {
   title: string;
   description: string;
   disabled?: boolean;
+  /** Highlights a choice the user is expected to confirm. */
+  selected?: boolean;
   onClick?: () => void;
 };
 
 const CardButtonVariant = Card.withComponent("button");
 
 export default function ButtonCard(props: ButtonCardProps) {
-  const { icon, title, description, disabled, onClick } = props;
+  const { icon, title, description, disabled, selected, onClick } = props;
 
   return (
     <CardButtonVariant
@@ -34,6 +36,8 @@ export default function ButtonCard(props: ButtonCardProps) {
         &:disabled {
           color: #888;
         }
+
+        ${selected ? "outline: 2px solid #888;" : ""}
       `}
       onClick={onClick}
       disabled={disabled}
@@ -1,8 +1,8 @@
 import { useSetAtom } from "jotai";
-import { useCallback } from "react";
+import { useCallback, useEffect } from "react";
 import { TbUpload } from "react-icons/tb";
 import { css } from "@emotion/react";
-import { setFileToSendAtom } from "@/stores/atoms";
+import { setFileToSendAtom, setImageBytesToSendAtom } from "@/stores/atoms";
 import { useLocalized } from "@/i18n";
 import { useFileOpenRequest } from "./file-handler";
 
@@ -22,6 +22,29 @@ export default function FileDrop() {
     onDropHandler: onDrop,
   });
 
+  const setImageBytesToSend = useSetAtom(setImageBytesToSendAtom);
+
+  useEffect(() => {
+    const onPaste = async (event: ClipboardEvent) => {
+      const image = Array.from(event.clipboardData?.files ?? []).find((file) =>
+        file.type.startsWith("image/"),
+      );
+
+      if (image === undefined) {
+        return;
+      }
+
+      event.preventDefault();
+      setImageBytesToSend(new Uint8Array(await image.arrayBuffer()));
+    };
+
+    window.addEventListener("paste", onPaste);
+
+    return () => {
+      window.removeEventListener("paste", onPaste);
+    };
+  }, [setImageBytesToSend]);
+
   if (!isOver) {
     return null;
   }
@@ -9,6 +9,7 @@ import Card, {
   CardIcon,
   CardTitle,
 } from "@/components/ui/Card";
+import { SendRequestEventMode } from "@/bindings.gen";
 import { SendState } from "@/stores/atoms";
 import { useLocalized } from "@/i18n";
 import ImageFilePicker from "./ImageFilePicker";
@@ -20,6 +21,7 @@ import { extractImageProps } from "./extractImageProps";
 type SendPageComponentProps = {
   sendState: SendState | undefined;
   pickedFilePath: string | undefined;
+  preselectedMode: SendRequestEventMode | null;
   imageFileSrc: string | undefined;
   imageValidity: "valid" | "invalid" | "pending";
   shouldCopyAfterUpload: boolean;
@@ -34,6 +36,7 @@ export default function SendPageComponent(props: SendPageComponentProps) {
   const {
     sendState,
     pickedFilePath,
+    preselectedMode,
     imageFileSrc,
     imageValidity,
     onFilePicked,
@@ -186,6 +189,7 @@ export default function SendPageComponent(props: SendPageComponentProps) {
             description={localized("send.send-to-video-player.description")}
             onClick={onSendToVideoPlayerClicked}
             disabled={imageValidity !== "valid"}
+            selected={preselectedMode === "UploadImageToVideoServer"}
           />
           <ButtonCard
             icon={<TbPhotoUp />}
@@ -193,6 +197,7 @@ export default function SendPageComponent(props: SendPageComponentProps) {
             description={localized("send.send-to-image-viewer.description")}
             onClick={onSendToImageViewerClicked}
             disabled={imageValidity !== "valid"}
+            selected={preselectedMode === "UploadImageToImageServer"}
           />
           <ButtonCard
             icon={<TbPrinter />}
@@ -200,6 +205,7 @@ export default function SendPageComponent(props: SendPageComponentProps) {
             description={localized("send.print-to-vrchat-print.description")}
             onClick={onSendToVrchatPrintClicked}
             disabled={imageValidity !== "valid"}
+            selected={preselectedMode === "UploadImageToVRChatPrint"}
           />
         </div>
       </div>
@@ -4,11 +4,18 @@ import { useMemo } from "react";
 import { commands } from "@/bindings.gen";
 
 export function useImageValidity(
-  fileSendRequest: { filePath: string; requestedAt: number } | undefined,
+  fileSendRequest:
+    | { filePath: string; bytes?: Uint8Array; requestedAt: number }
+    | undefined,
 ) {
   const isAbleToReadAtom = useMemo(
     () =>
       atom(async () => {
+        // NOTE: Pasted images are decoded by the backend when they are sent.
+        if (fileSendRequest?.bytes) {
+          return true;
+        }
+
         if (!fileSendRequest?.filePath) {
           return false;
         }
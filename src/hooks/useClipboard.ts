@@ -1,9 +1,19 @@
 import { writeText } from "@tauri-apps/plugin-clipboard-manager";
+import { writeClipboardImage } from "@/ipc";
 
 export default function useClipboard() {
   return {
     writeText: async (text: string) => {
       await writeText(text);
     },
+    writeImage: async (bytes: Uint8Array) => {
+      const result = await writeClipboardImage(bytes);
+
+      if (result.status === "error") {
+        throw new Error(
+          `Failed to write image to clipboard: ${result.error.message}`,
+        );
+      }
+    },
   };
 }
@@ -1,5 +1,46 @@
 import { invoke } from "@tauri-apps/api/core";
+import type {
+  AppError,
+  Result,
+  SendRequestEventMode,
+} from "@/bindings.gen";
 
 export async function openResourceDir() {
   await invoke("open_resource_dir");
 }
+
+/** Sends image bytes as the raw request body instead of a JSON number array. */
+export async function sendImageBytes(
+  bytes: Uint8Array,
+  mode: SendRequestEventMode,
+): Promise<Result<string | null, AppError>> {
+  try {
+    return {
+      status: "ok",
+      data: await invoke<string | null>("send_image_bytes", bytes, {
+        headers: { mode },
+      }),
+    };
+  } catch (e) {
+    if (e instanceof Error) throw e;
+    else return { status: "error", error: e as AppError };
+  }
+}
+
+/**
+ * Puts an encoded image on the clipboard as the raw request body. Use this
+ * instead of the clipboard plugin so the clipboard watcher ignores it.
+ */
+export async function writeClipboardImage(
+  bytes: Uint8Array,
+): Promise<Result<null, AppError>> {
+  try {
+    return {
+      status: "ok",
+      data: await invoke<null>("write_clipboard_image", bytes),
+    };
+  } catch (e) {
+    if (e instanceof Error) throw e;
+    else return { status: "error", error: e as AppError };
+  }
+}
@@ -80,7 +80,12 @@ function RouteComponent() {
       };
 
       commands
-        .finishCaptureWithCroppedRect(webviewWidowLabel, rect, nextAction)
+        .finishCaptureWithCroppedRect(
+          webviewWidowLabel,
+          rect,
+          nextAction,
+          null,
+        )
         .catch(console.error);
     },
     [webviewWidowLabel, boundingClientRect, boundingRectInRootCssPixel],
@@ -99,7 +104,7 @@ function RouteComponent() {
     };
 
     commands
-      .finishCaptureWithCroppedRect(webviewWidowLabel, rect, "Ask")
+      .finishCaptureWithCroppedRect(webviewWidowLabel, rect, "Ask", null)
       .catch(console.error);
   }, [webviewWidowLabel]);
 
@@ -1,7 +1,7 @@
 import { getDefaultStore } from "jotai";
 import { useEffect, useState } from "react";
 import { createFileRoute } from "@tanstack/react-router";
-import { setFileToSendAtom } from "@/stores/atoms";
+import { queueSendRequestsAtom } from "@/stores/atoms";
 import router from "@/stores/router";
 import { commands } from "@/bindings.gen";
 
@@ -48,12 +48,22 @@ async function launch() {
         replace: true,
       });
       break;
-    case "Send":
+    case "Send": {
+      const { files, options } = launchOptions.data.mode.args;
+
       getDefaultStore().set(
-        setFileToSendAtom,
-        launchOptions.data.mode.args.file,
+        queueSendRequestsAtom,
+        files.map((file) => ({
+          file,
+          mode: options.mode,
+          preselectedMode: options.preselected_mode,
+          copy: options.copy,
+        })),
       );
       break;
+    }
+    case "Help":
+    case "Version":
     case "Default":
       router.navigate({
         to: "/",
@@ -1,5 +1,5 @@
 import { useAtom, useAtomValue, useSetAtom } from "jotai";
-import { useCallback, useMemo } from "react";
+import { useCallback, useEffect, useMemo } from "react";
 import { createFileRoute } from "@tanstack/react-router";
 import AppLayout from "@/components/layout/AppContainer";
 import convertFreshFileSrc from "@/features/file/convertFreshFileSrc";
@@ -7,6 +7,9 @@ import SendPageComponent from "@/features/send-image/SendPageComponent";
 import { useImageValidity } from "@/features/send-image/useImageValidity";
 import {
   fileToSendAtom,
+  leaveSendPageAtom,
+  preselectedModeAtom,
+  sendImageBytesAtom,
   sendImageToImageViewerAtom,
   sendImageToVRChatPrintAtom,
   sendImageToVideoPlayerAtom,
@@ -25,6 +28,8 @@ function SendPage() {
 
   const sendState = useAtomValue(sendStateAtom);
 
+  const preselectedMode = useAtomValue(preselectedModeAtom);
+
   const localized = useLocalized();
 
   const sendImageToVideoPlayer = useSetAtom(sendImageToVideoPlayerAtom);
@@ -33,14 +38,25 @@ function SendPage() {
 
   const imageValidity = useImageValidity(pickedFilePath);
 
+  const leaveSendPage = useSetAtom(leaveSendPageAtom);
+
+  useEffect(() => leaveSendPage, [leaveSendPage]);
+
+  const sendImageBytes = useSetAtom(sendImageBytesAtom);
+
   const onSendToVideoPlayerClicked = useCallback(() => {
     if (!pickedFilePath) {
       alert(localized("send.no-file-selected"));
       return;
     }
 
+    if (pickedFilePath.bytes) {
+      sendImageBytes(pickedFilePath.bytes, "UploadImageToVideoServer");
+      return;
+    }
+
     sendImageToVideoPlayer(pickedFilePath.filePath);
-  }, [pickedFilePath, localized, sendImageToVideoPlayer]);
+  }, [pickedFilePath, localized, sendImageBytes, sendImageToVideoPlayer]);
 
   const onSendToImageViewerClicked = useCallback(() => {
     if (!pickedFilePath) {
@@ -48,8 +64,13 @@ function SendPage() {
       return;
     }
 
+    if (pickedFilePath.bytes) {
+      sendImageBytes(pickedFilePath.bytes, "UploadImageToImageServer");
+      return;
+    }
+
     sendImageToImageViewer(pickedFilePath.filePath);
-  }, [pickedFilePath, localized, sendImageToImageViewer]);
+  }, [pickedFilePath, localized, sendImageBytes, sendImageToImageViewer]);
 
   const onSendToVrchatPrintClicked = useCallback(() => {
     if (!pickedFilePath) {
@@ -57,8 +78,13 @@ function SendPage() {
       return;
     }
 
+    if (pickedFilePath.bytes) {
+      sendImageBytes(pickedFilePath.bytes, "UploadImageToVRChatPrint");
+      return;
+    }
+
     sendImageToVRChatPrint(pickedFilePath.filePath);
-  }, [pickedFilePath, localized, sendImageToVRChatPrint]);
+  }, [pickedFilePath, localized, sendImageBytes, sendImageToVRChatPrint]);
 
   const setFileToSend = useSetAtom(setFileToSendAtom);
 
@@ -71,10 +97,21 @@ function SendPage() {
     [setFileToSend],
   );
 
-  const imageFileSrc = useMemo(
-    () => pickedFilePath && convertFreshFileSrc(pickedFilePath.filePath),
-    [pickedFilePath],
-  );
+  const imageFileSrc = useMemo(() => {
+    if (pickedFilePath?.bytes) {
+      return URL.createObjectURL(new Blob([pickedFilePath.bytes]));
+    }
+
+    return pickedFilePath && convertFreshFileSrc(pickedFilePath.filePath);
+  }, [pickedFilePath]);
+
+  useEffect(() => {
+    return () => {
+      if (imageFileSrc?.startsWith("blob:")) {
+        URL.revokeObjectURL(imageFileSrc);
+      }
+    };
+  }, [imageFileSrc]);
 
   const [shouldCopyAfterUpload, setShouldCopyAfterUpload] = useAtom(
     shouldCopyAfterUploadAtom,
@@ -85,6 +122,7 @@ function SendPage() {
       <SendPageComponent
         sendState={sendState}
         pickedFilePath={pickedFilePath?.filePath}
+        preselectedMode={preselectedMode}
         imageFileSrc={imageFileSrc}
         imageValidity={imageValidity}
         shouldCopyAfterUpload={shouldCopyAfterUpload}
@@ -2,7 +2,8 @@ import { atom } from "jotai";
 import { writeText } from "@tauri-apps/plugin-clipboard-manager";
 import { vrchatLoginTaskAtom } from "@/features/send-image/stores/vrchat-login";
 import router from "@/stores/router";
-import { commands } from "@/bindings.gen";
+import { SendRequestEventMode, commands } from "@/bindings.gen";
+import { sendImageBytes } from "@/ipc";
 import {
   shouldCopyAfterUploadAtom,
   uploaderApiKeyAtom,
@@ -64,8 +65,9 @@ export type SendState =
 
 export const sendStateAtom = atom<SendState | undefined>();
 
+// NOTE: `bytes` is set instead of a file path for images pasted into the window.
 const fileToSendBaseAtom = atom<
-  { filePath: string; requestedAt: number } | undefined
+  { filePath: string; bytes?: Uint8Array; requestedAt: number } | undefined
 >();
 
 export const fileToSendAtom = atom(
@@ -77,6 +79,9 @@ export const fileToSendAtom = atom(
   },
 );
 
+/** Mode highlighted on the send page for the user to confirm, e.g. from a deep link. */
+export const preselectedModeAtom = atom<SendRequestEventMode | null>(null);
+
 export const setFileToSendAtom = atom(null, (get, set, filePath: string) => {
   const sendState = get(sendStateAtom);
   if (sendState !== undefined && sendState.state.status === "uploading") {
@@ -86,11 +91,28 @@ export const setFileToSendAtom = atom(null, (get, set, filePath: string) => {
   set(fileToSendAtom, filePath);
   router.navigate({ href: "/send" });
   set(sendStateAtom, undefined);
+  set(preselectedModeAtom, null);
 });
 
+/** Like `setFileToSendAtom`, for image bytes pasted into the window. */
+export const setImageBytesToSendAtom = atom(
+  null,
+  (get, set, bytes: Uint8Array) => {
+    const sendState = get(sendStateAtom);
+    if (sendState !== undefined && sendState.state.status === "uploading") {
+      return;
+    }
+
+    set(fileToSendBaseAtom, { filePath: "", bytes, requestedAt: Date.now() });
+    router.navigate({ href: "/send" });
+    set(sendStateAtom, undefined);
+    set(preselectedModeAtom, null);
+  },
+);
+
 export const sendImageToVideoPlayerAtom = atom(
   null,
-  async (get, set, filePath: string) => {
+  async (get, set, filePath: string, copy?: boolean | null) => {
     set(sendStateAtom, {
       mode: "video_player",
       state: { status: "uploading" },
@@ -132,7 +154,7 @@ export const sendImageToVideoPlayerAtom = atom(
 
         const url = result.data;
 
-        if (await get(shouldCopyAfterUploadAtom)) {
+        if (copy ?? (await get(shouldCopyAfterUploadAtom))) {
           await writeText(url);
         }
 
@@ -152,13 +174,15 @@ export const sendImageToVideoPlayerAtom = atom(
         mode: "video_player",
         state: { status: "error", message: String(err) },
       });
+    } finally {
+      set(sendNextQueuedRequestAtom);
     }
   },
 );
 
 export const sendImageToImageViewerAtom = atom(
   null,
-  async (get, set, filePath: string) => {
+  async (get, set, filePath: string, copy?: boolean | null) => {
     set(sendStateAtom, {
       mode: "image_viewer",
       state: { status: "uploading" },
@@ -200,7 +224,7 @@ export const sendImageToImageViewerAtom = atom(
 
         const url = result.data;
 
-        if (await get(shouldCopyAfterUploadAtom)) {
+        if (copy ?? (await get(shouldCopyAfterUploadAtom))) {
           await writeText(url);
         }
 
@@ -220,6 +244,8 @@ export const sendImageToImageViewerAtom = atom(
         mode: "image_viewer",
         state: { status: "error", message: String(err) },
       });
+    } finally {
+      set(sendNextQueuedRequestAtom);
     }
   },
 );
@@ -266,8 +292,154 @@ export const sendImageToVRChatPrintAtom = atom(
         mode: "vrchat_print",
         state: { status: "error", message: String(err) },
       });
+    } finally {
+      set(sendNextQueuedRequestAtom);
     }
   },
 );
 
+const sendStateModes = {
+  UploadImageToVideoServer: "video_player",
+  UploadImageToImageServer: "image_viewer",
+  UploadImageToVRChatPrint: "vrchat_print",
+} as const satisfies Record<SendRequestEventMode, SendState["mode"]>;
+
+/** Sends pasted image bytes without writing them to a file in the frontend. */
+export const sendImageBytesAtom = atom(
+  null,
+  async (get, set, bytes: Uint8Array, mode: SendRequestEventMode) => {
+    const sendStateMode = sendStateModes[mode];
+
+    set(sendStateAtom, {
+      mode: sendStateMode,
+      state: { status: "uploading" },
+    });
+
+    try {
+      for (let i = 0; i < 3; i++) {
+        const result = await sendImageBytes(bytes, mode);
+
+        if (result.status === "error") {
+          if (result.error.type === "UploaderAuthRequired") {
+            await new Promise((resolve, reject) =>
+              set(registerRequestAtom, { resolve, reject }),
+            );
+
+            continue; // Retry
+          }
+
+          if (result.error.type === "VrchatAuthRequired") {
+            await new Promise((resolve, reject) =>
+              set(vrchatLoginTaskAtom, { resolve, reject }),
+            );
+
+            continue; // Retry
+          }
+
+          throw new Error(
+            `アップロードに失敗しました: ${result.error.type} ${result.error.message}`,
+          );
+        }
+
+        if (sendStateMode === "vrchat_print") {
+          set(sendStateAtom, {
+            mode: sendStateMode,
+            state: { status: "done" },
+          });
+
+          return;
+        }
+
+        const url = result.data;
+
+        if (url === null) {
+          throw new Error("アップロードに失敗しました: URL がありません");
+        }
+
+        if (await get(shouldCopyAfterUploadAtom)) {
+          await writeText(url);
+        }
+
+        set(sendStateAtom, {
+          mode: sendStateMode,
+          state: { status: "done", url: url },
+        });
+
+        return;
+      }
+
+      throw new Error(
+        "アップロードに失敗しました: 不明な原因により失敗しました",
+      );
+    } catch (err) {
+      set(sendStateAtom, {
+        mode: sendStateMode,
+        state: { status: "error", message: String(err) },
+      });
+    } finally {
+      set(sendNextQueuedRequestAtom);
+    }
+  },
+);
+
+export type SendRequest = {
+  file: string;
+  mode: SendRequestEventMode | null;
+  preselectedMode: SendRequestEventMode | null;
+  copy: boolean | null;
+};
+
+const queuedSendRequestsAtom = atom<SendRequest[]>([]);
+
+// NOTE: True while a queued request is on the send page, waiting for the user or uploading.
+const isSendingQueuedRequestAtom = atom(false);
+
+/** Shows the requests on the send page one after another, sending the ones with a mode right away. */
+export const queueSendRequestsAtom = atom(
+  null,
+  (get, set, requests: SendRequest[]) => {
+    set(queuedSendRequestsAtom, [...get(queuedSendRequestsAtom), ...requests]);
+
+    if (
+      !get(isSendingQueuedRequestAtom) &&
+      get(sendStateAtom)?.state.status !== "uploading"
+    ) {
+      set(sendNextQueuedRequestAtom);
+    }
+  },
+);
+
+const sendNextQueuedRequestAtom = atom(null, (get, set) => {
+  const [request, ...rest] = get(queuedSendRequestsAtom);
+
+  set(queuedSendRequestsAtom, rest);
+  set(isSendingQueuedRequestAtom, request !== undefined);
+
+  if (request === undefined) {
+    return;
+  }
+
+  set(setFileToSendAtom, request.file);
+  set(preselectedModeAtom, request.preselectedMode);
+
+  switch (request.mode) {
+    case "UploadImageToVideoServer":
+      set(sendImageToVideoPlayerAtom, request.file, request.copy);
+      break;
+    case "UploadImageToImageServer":
+      set(sendImageToImageViewerAtom, request.file, request.copy);
+      break;
+    case "UploadImageToVRChatPrint":
+      set(sendImageToVRChatPrintAtom, request.file);
+      break;
+  }
+});
+
+/** Lets the next request in when the user leaves the send page without sending. */
+export const leaveSendPageAtom = atom(null, (get, set) => {
+  if (get(sendStateAtom)?.state.status !== "uploading") {
+    set(isSendingQueuedRequestAtom, false);
+  }
+});
+
 export const registerRequestAtom = createTaskAtom<void>();
@@ -20,6 +20,7 @@ type Story = StoryObj<typeof meta>;
 const defaultArgs = {
   sendState: undefined,
   pickedFilePath: undefined,
+  preselectedMode: null,
   imageFileSrc: undefined,
   imageValidity: "pending",
   shouldCopyAfterUpload: true,
@@ -36,6 +37,14 @@ export const Default: Story = {
   },
 };
 
+export const PreselectedMode: Story = {
+  args: {
+    ...defaultArgs,
+    imageValidity: "valid",
+    preselectedMode: "UploadImageToVRChatPrint",
+  },
+};
+
 export const UploadingImage: Story = {
   args: {
     ...defaultArgs,